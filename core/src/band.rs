@@ -0,0 +1,770 @@
+// The bands of a resistor, capacitor or inductor, and the value they read as
+
+use crate::colour::*;
+use crate::eseries::*;
+use crate::letters::*;
+use crate::tables::*;
+use crate::value::*;
+
+pub const MAX_BANDS: u8 = 6;
+pub const MIN_BANDS: u8 = 3;
+// A zero ohm link, which only has a single black band
+pub const JUMPER_BANDS: u8 = 1;
+pub const MIL_BANDS: u8 = 5;
+pub const MAX_CAPACITOR_BANDS: u8 = 5;
+pub const MAX_INDUCTOR_BANDS: u8 = 4;
+
+// What a value measures, deciding the prefixes and sign drawn with it
+#[derive(Clone, Copy, PartialEq)]
+pub enum Unit {
+    Ohms,
+    Volts,
+    Amps,
+    Farads,
+    Henries,
+}
+
+impl Unit {
+    // SI prefixes from a thousandth of the stored unit upwards
+    // Capacitance is stored in pF and inductance in µH, as that's what their codes count in
+    pub const fn prefixes(self) -> &'static [&'static str; 5] {
+        match self {
+            Unit::Farads => &CAPACITOR_PREFIXES,
+            Unit::Henries => &INDUCTOR_PREFIXES,
+            _ => &PREFIXES,
+        }
+    }
+
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Unit::Ohms => "\0",
+            Unit::Volts => "V\0",
+            Unit::Amps => "A\0",
+            Unit::Farads => "F\0",
+            Unit::Henries => "H\0",
+        }
+    }
+}
+
+// What kind of part the bands are on
+#[derive(Clone, Copy, PartialEq)]
+pub enum Component {
+    Resistor,
+    Capacitor,
+    Inductor,
+}
+
+impl Component {
+    pub const fn unit(self) -> Unit {
+        match self {
+            Component::Resistor => Unit::Ohms,
+            Component::Capacitor => Unit::Farads,
+            Component::Inductor => Unit::Henries,
+        }
+    }
+
+    pub const fn max_bands(self) -> u8 {
+        match self {
+            Component::Resistor => MAX_BANDS,
+            Component::Capacitor => MAX_CAPACITOR_BANDS,
+            Component::Inductor => MAX_INDUCTOR_BANDS,
+        }
+    }
+}
+
+// What the bands can represent
+pub enum ValType {
+    Digit,
+    Multiplier,
+    Tolerance,
+    TCR,
+    CapMultiplier,
+    CapTolerance,
+    Voltage,
+    InductorMultiplier,
+    InductorTolerance,
+    Jumper,
+    FailureRate,
+}
+
+// A single band of the resistor
+pub struct Band {
+    pub value: i8,
+    pub show: bool,
+    pub vtype: ValType,
+}
+
+impl Band {
+    pub const fn new(show: bool, vtype: ValType) -> Self {
+        Band {
+            value: 0,
+            show,
+            vtype,
+        }
+    }
+
+    // How far the value is from the index of its colour, as multipliers start below 1
+    pub const fn offset(vtype: &ValType) -> i8 {
+        match vtype {
+            ValType::Multiplier => -MIN_POW,
+            ValType::CapMultiplier => -MIN_CAPACITOR_POW,
+            ValType::InductorMultiplier => -MIN_INDUCTOR_POW,
+            _ => 0,
+        }
+    }
+
+    // Lowest and highest values, one for each colour
+    pub fn range(&self) -> (i8, i8) {
+        let offset = Band::offset(&self.vtype);
+        let len = Band::colours_from_valtype(&self.vtype).len() as i8;
+        (-offset, len - 1 - offset)
+    }
+
+    pub fn change_by(&mut self, increment: i8) {
+        let new = self.value + increment;
+
+        if let ValType::Digit = self.vtype {
+            self.value = new.rem_euclid(10);
+            return;
+        }
+        let (min, max) = self.range();
+
+        if new <= max && new >= min {
+            self.value = new
+        }
+    }
+
+    pub fn change_to(&mut self, new: i8) {
+        self.value = new - Band::offset(&self.vtype)
+    }
+
+    pub fn get_pointer(&self) -> i8 {
+        self.value + Band::offset(&self.vtype)
+    }
+
+    // Whether the band is part of the resistance value, rather than its accuracy
+    pub fn is_value(&self) -> bool {
+        matches!(
+            self.vtype,
+            ValType::Digit
+                | ValType::Multiplier
+                | ValType::CapMultiplier
+                | ValType::InductorMultiplier
+        )
+    }
+
+    // Tolerance in hundredths of a percent
+    pub fn tolerance(&self) -> u16 {
+        match self.vtype {
+            ValType::CapTolerance => CAPACITOR_TOLERANCE_VALUES[self.value as usize],
+            ValType::InductorTolerance => INDUCTOR_TOLERANCE_VALUES[self.value as usize],
+            _ => TOLERANCE_VALUES[self.value as usize],
+        }
+    }
+
+    // Letter code for the band's colour, for the bands that have them
+    pub fn letter(&self) -> Option<&'static str> {
+        letters(&self.vtype).map(|letters| letters[self.get_pointer() as usize])
+    }
+
+    pub fn tolerance_str(&self) -> &str {
+        match self.vtype {
+            ValType::CapTolerance => CAPACITOR_TOLERANCES[self.value as usize],
+            ValType::InductorTolerance => INDUCTOR_TOLERANCES[self.value as usize],
+            _ => TOLERANCES[self.value as usize],
+        }
+    }
+
+    pub fn colour(&self) -> Colour {
+        Band::colours_from_valtype(&self.vtype)[self.get_pointer() as usize]
+    }
+
+    pub fn colours_from_valtype(vtype: &ValType) -> &'static [Colour] {
+        match vtype {
+            ValType::Digit => &VALUE_COLORS,
+            ValType::Multiplier => &MULTIPLIER_COLORS,
+            ValType::Tolerance => &TOLERANCE_COLORS,
+            ValType::TCR => &TCR_COLORS,
+            ValType::CapMultiplier => &CAPACITOR_MULTIPLIER_COLORS,
+            ValType::CapTolerance => &CAPACITOR_TOLERANCE_COLORS,
+            ValType::Voltage => &VOLTAGE_COLORS,
+            ValType::InductorMultiplier => &INDUCTOR_MULTIPLIER_COLORS,
+            ValType::InductorTolerance => &INDUCTOR_TOLERANCE_COLORS,
+            ValType::Jumper => &JUMPER_COLORS,
+            ValType::FailureRate => &FAILURE_RATE_COLORS,
+        }
+    }
+}
+
+// All bands of the resistor, or of another banded component
+pub struct Resistance {
+    pub value1: Band,
+    pub value10: Band,
+    pub value100: Band,
+    pub multiplier_pow: Band,
+    pub tolerance_index: Band,
+    // Voltage rating on capacitors
+    pub tcr_index: Band,
+    pub bands: u8,
+    pub component: Component,
+    // 5 band military layout, with a failure rate band in place of the third digit
+    pub mil: bool,
+}
+
+// The colours of a resistor without its layout, for keeping several at once
+#[derive(Clone, Copy)]
+pub struct ColourCode {
+    pub bands: u8,
+    pub values: [i8; 6],
+    pub component: Component,
+    pub mil: bool,
+}
+
+impl ColourCode {
+    pub const fn new(bands: u8) -> Self {
+        ColourCode {
+            bands,
+            values: [0; 6],
+            component: Component::Resistor,
+            mil: false,
+        }
+    }
+}
+
+impl Resistance {
+    pub const fn new(bands: u8) -> Self {
+        Resistance::with_component(Component::Resistor, bands)
+    }
+
+    pub const fn with_component(component: Component, bands: u8) -> Self {
+        Resistance::layout(component, bands, false)
+    }
+
+    pub const fn mil() -> Self {
+        Resistance::layout(Component::Resistor, MIL_BANDS, true)
+    }
+
+    pub const fn layout(component: Component, bands: u8, mil: bool) -> Self {
+        if bands == JUMPER_BANDS {
+            return Resistance::jumper();
+        }
+
+        // Which bands are shown, capacitors and inductors only ever have 2 digits
+        let resistor = matches!(component, Component::Resistor);
+        let three_digits = resistor && !mil && bands >= 5;
+        let tolerance = bands >= 4;
+        let last = if resistor && !mil {
+            bands >= 6
+        } else {
+            bands >= 5
+        };
+
+        // Inductors never have a band after the tolerance
+        let (multiplier_type, tolerance_type, last_type) = match component {
+            Component::Resistor if mil => (
+                ValType::Multiplier,
+                ValType::Tolerance,
+                ValType::FailureRate,
+            ),
+            Component::Resistor => (ValType::Multiplier, ValType::Tolerance, ValType::TCR),
+            Component::Capacitor => (
+                ValType::CapMultiplier,
+                ValType::CapTolerance,
+                ValType::Voltage,
+            ),
+            Component::Inductor => (
+                ValType::InductorMultiplier,
+                ValType::InductorTolerance,
+                ValType::TCR,
+            ),
+        };
+
+        Resistance {
+            value1: Band::new(true, ValType::Digit),
+            value10: Band::new(true, ValType::Digit),
+            value100: Band::new(three_digits, ValType::Digit),
+            multiplier_pow: Band::new(true, multiplier_type),
+            tolerance_index: Band::new(tolerance, tolerance_type),
+            tcr_index: Band::new(last, last_type),
+
+            bands,
+            component,
+            mil,
+        }
+    }
+
+    // A zero ohm link, with one black band in the middle
+    pub const fn jumper() -> Self {
+        let mut res = Resistance::new(MIN_BANDS);
+        res.value1 = Band::new(true, ValType::Jumper);
+        res.value10.show = false;
+        res.multiplier_pow.show = false;
+        res.bands = JUMPER_BANDS;
+        res
+    }
+
+    // A preferred value with the tolerance its series is made for
+    // Series without a tolerance band give a 3 band resistor
+    pub fn from_preferred(decimal: Decimal, series: Series) -> Result<Self, ValueError> {
+        let tolerance = TOLERANCE_VALUES
+            .iter()
+            .position(|&tolerance| tolerance == series.tolerance());
+        let bands = match (series.figures(), tolerance) {
+            (3, _) => 5,
+            (_, Some(_)) => 4,
+            (_, None) => 3,
+        };
+
+        let mut res = Resistance::new(bands);
+        res.set_decimal(decimal)?;
+        if let Some(tolerance) = tolerance {
+            res.tolerance_index.value = tolerance as i8;
+        }
+        Ok(res)
+    }
+
+    pub fn code(&self) -> ColourCode {
+        ColourCode {
+            bands: self.bands,
+            component: self.component,
+            mil: self.mil,
+            values: [
+                self.value1.value,
+                self.value10.value,
+                self.value100.value,
+                self.multiplier_pow.value,
+                self.tolerance_index.value,
+                self.tcr_index.value,
+            ],
+        }
+    }
+
+    pub fn from_code(code: &ColourCode) -> Self {
+        let mut res = Resistance::layout(code.component, code.bands, code.mil);
+        res.value1.value = code.values[0];
+        res.value10.value = code.values[1];
+        res.value100.value = code.values[2];
+        res.multiplier_pow.value = code.values[3];
+        res.tolerance_index.value = code.values[4];
+        res.tcr_index.value = code.values[5];
+        res
+    }
+
+    // Every band in order along the part, including those that aren't shown
+    pub fn all(&self) -> [&Band; 6] {
+        [
+            &self.value1,
+            &self.value10,
+            &self.value100,
+            &self.multiplier_pow,
+            &self.tolerance_index,
+            &self.tcr_index,
+        ]
+    }
+
+    fn all_mut(&mut self) -> [&mut Band; 6] {
+        [
+            &mut self.value1,
+            &mut self.value10,
+            &mut self.value100,
+            &mut self.multiplier_pow,
+            &mut self.tolerance_index,
+            &mut self.tcr_index,
+        ]
+    }
+
+    // Where the ith band shown is among all of them
+    pub fn slot(&self, i: u8) -> usize {
+        self.all()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, band)| band.show)
+            .nth(i as usize)
+            .unwrap()
+            .0
+    }
+
+    // An easier way to index through the bands that are shown
+    #[allow(clippy::should_implement_trait)]
+    pub fn index(&self, i: u8) -> &Band {
+        self.all()[self.slot(i)]
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn index_mut(&mut self, i: u8) -> &mut Band {
+        let slot = self.slot(i);
+        self.all_mut().into_iter().nth(slot).unwrap()
+    }
+
+    // The significant figures read from the digit bands
+    pub fn digits(&self) -> u16 {
+        let mut digits = self.value1.value as u16 * 10 + self.value10.value as u16;
+        if self.value100.show {
+            digits = digits * 10 + self.value100.value as u16;
+        }
+        digits
+    }
+
+    pub fn figures(&self) -> u8 {
+        if self.value100.show {
+            3
+        } else {
+            2
+        }
+    }
+
+    pub fn decimal(&self) -> Decimal {
+        Decimal::new(self.digits() as u32, self.multiplier_pow.value)
+    }
+
+    // The value as the bands would give it, or why they can't
+    pub fn fit(&self, decimal: Decimal) -> Result<Decimal, ValueError> {
+        // A jumper can only ever be zero
+        if self.bands == JUMPER_BANDS {
+            return if decimal.sig == 0 {
+                Ok(decimal)
+            } else {
+                Err(ValueError::OutOfRange)
+            };
+        }
+
+        let (min_pow, max_pow) = self.multiplier_pow.range();
+        decimal.fit_within(self.figures(), min_pow, max_pow)
+    }
+
+    // Sets the digit and multiplier bands, leaving the rest untouched
+    pub fn set_decimal(&mut self, decimal: Decimal) -> Result<(), ValueError> {
+        let fitted = self.fit(decimal)?;
+        if self.bands == JUMPER_BANDS {
+            return Ok(());
+        }
+
+        let mut sig = fitted.sig;
+        if self.value100.show {
+            self.value100.value = (sig % 10) as i8;
+            sig /= 10;
+        }
+        self.value10.value = (sig % 10) as i8;
+        self.value1.value = (sig / 10) as i8;
+        self.multiplier_pow.value = fitted.pow;
+        Ok(())
+    }
+
+    // Exact resistance in milliohms, as the multiplier can go down to 0.001
+    pub fn ohms(&self) -> Milliohms {
+        self.decimal().milliohms()
+    }
+
+    // Tolerance in hundredths of a percent, 3 band resistors don't have a band for it
+    pub fn tolerance(&self) -> u16 {
+        if self.tolerance_index.show {
+            self.tolerance_index.tolerance()
+        } else {
+            NO_BAND_TOLERANCE
+        }
+    }
+
+    pub fn tolerance_str(&self) -> &str {
+        if self.tolerance_index.show {
+            self.tolerance_index.tolerance_str()
+        } else {
+            "20\0"
+        }
+    }
+
+    pub fn tolerance_letter(&self) -> &str {
+        if self.tolerance_index.show {
+            self.tolerance_index.letter().unwrap_or("\0")
+        } else {
+            NO_BAND_TOLERANCE_LETTER
+        }
+    }
+
+    // Lowest and highest resistance the part could be, in milliohms
+    pub fn bounds(&self) -> (Milliohms, Milliohms) {
+        let ohms = self.ohms();
+        let spread = parts_per_million(ohms, self.tolerance() as u32 * 100);
+        (ohms - spread, ohms + spread)
+    }
+
+    // Temperature coefficient in ppm/K, only 6 band resistors have a band for it
+    pub fn tcr(&self) -> Option<u16> {
+        if self.tcr_index.show && matches!(self.tcr_index.vtype, ValType::TCR) {
+            Some(TCR_VALUES[self.tcr_index.value as usize])
+        } else {
+            None
+        }
+    }
+
+    // Worst case bounds at a temperature, drifting either way from the reference temperature
+    pub fn drift_bounds(&self, tcr: u16, temp: i16) -> (Milliohms, Milliohms) {
+        let (min, max) = self.bounds();
+        let ppm = tcr as u32 * (temp - REFERENCE_TEMP).unsigned_abs() as u32;
+        (
+            min - parts_per_million(min, ppm),
+            max + parts_per_million(max, ppm),
+        )
+    }
+
+    // The series a part with this tolerance would be made in, if the value isn't in it
+    // Values that don't exist as real parts usually mean a band has been misread
+    pub fn unexpected_series(&self) -> Option<Series> {
+        let value = self.decimal();
+        if self.component != Component::Resistor || value.sig == 0 {
+            return None;
+        }
+
+        let tolerance = self.tolerance();
+        let series = Series::for_tolerance(tolerance);
+        // Tight tolerance parts are commonly made in E24 values as well
+        let e24 = tolerance <= Series::E48.tolerance() && Series::E24.contains(value);
+        if series.contains(value) || e24 {
+            None
+        } else {
+            Some(series)
+        }
+    }
+
+    // The same colours read from the other end, if that's also a valid code
+    pub fn reversed(&self) -> Option<Resistance> {
+        if self.bands < MIN_BANDS {
+            return None;
+        }
+
+        let mut reversed = Resistance::layout(self.component, self.bands, self.mil);
+        for i in 0..self.bands {
+            let colour = self.index(self.bands - 1 - i).colour();
+            let band = reversed.index_mut(i);
+            let position = Band::colours_from_valtype(&band.vtype)
+                .iter()
+                .position(|&other| other == colour)?;
+            band.change_to(position as i8);
+        }
+        Some(reversed)
+    }
+
+    // How believable the reading is, for picking between it and the reversed reading
+    // Parts are made in preferred values and common tolerances, and don't start with a zero
+    pub fn plausibility(&self) -> u8 {
+        let mut score = 0;
+        let series = if self.figures() == 3 {
+            Series::E192
+        } else {
+            Series::E24
+        };
+        if series.contains(self.decimal()) {
+            score += 2;
+        }
+        if self.tolerance_index.show && COMMON_TOLERANCES.contains(&self.tolerance()) {
+            score += 1;
+        }
+        if self.value1.value != 0 {
+            score += 1;
+        }
+        score
+    }
+
+    // Moves the value to the next number in a preferred series
+    // Values that need more digit bands than are shown are skipped over
+    pub fn step_series(&mut self, series: Series, up: bool) {
+        let mut next = self.decimal();
+        if next.sig == 0 {
+            next = Decimal::new(series.get(0) as u32, 0);
+            let _ = self.set_decimal(next);
+            return;
+        }
+
+        for _ in 0..series.len() {
+            let stepped = if up {
+                series.above(next)
+            } else {
+                series.below(next)
+            };
+            next = match stepped {
+                Some(stepped) => stepped,
+                None => return,
+            };
+            match self.set_decimal(next) {
+                Err(ValueError::TooPrecise) => continue,
+                _ => return,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ohms_from_the_bands() {
+        // Yellow violet red is 4.7kΩ
+        let mut res = Resistance::new(4);
+        res.value1.value = 4;
+        res.value10.value = 7;
+        res.multiplier_pow.value = 2;
+        assert_eq!(res.ohms(), 4_700_000);
+        // Silver takes it down to 0.47Ω
+        res.multiplier_pow.value = -2;
+        assert_eq!(res.ohms(), 470);
+    }
+
+    fn resistor(bands: u8, decimal: Decimal) -> Resistance {
+        let mut res = Resistance::new(bands);
+        res.set_decimal(decimal).unwrap();
+        res
+    }
+
+    #[test]
+    fn set_decimal_fills_the_bands() {
+        let res = resistor(5, Decimal::new(47, 2));
+        assert_eq!(res.digits(), 470);
+        assert_eq!(res.multiplier_pow.value, 1);
+        let res = resistor(4, Decimal::new(47, -2));
+        assert_eq!(res.digits(), 47);
+        assert_eq!(res.multiplier_pow.value, -2);
+        assert_eq!(
+            Resistance::new(4).set_decimal(Decimal::new(473, 0)),
+            Err(ValueError::TooPrecise)
+        );
+    }
+
+    #[test]
+    fn step_series_skips_values_the_bands_cannot_show() {
+        let mut res = resistor(4, Decimal::new(47, 2));
+        res.step_series(Series::E24, true);
+        assert_eq!(res.ohms(), 5_100_000);
+        // E96 values from 4.99k down to 3.48k need 3 digits
+        res.step_series(Series::E96, false);
+        assert_eq!(res.ohms(), 3_400_000);
+    }
+
+    fn set_tolerance(res: &mut Resistance, tolerance: u16) {
+        let index = TOLERANCE_VALUES
+            .iter()
+            .position(|&value| value == tolerance);
+        res.tolerance_index.value = index.unwrap() as i8;
+    }
+
+    #[test]
+    fn bounds_spread_by_the_tolerance() {
+        let mut res = resistor(4, Decimal::new(47, 2));
+        set_tolerance(&mut res, 500);
+        assert_eq!(res.bounds(), (4_465_000, 4_935_000));
+        // 3 bands have no tolerance band, so they're ±20%
+        let res = resistor(3, Decimal::new(1, 3));
+        assert_eq!(res.bounds(), (800_000, 1_200_000));
+        // The spread is rounded down to a whole milliohm
+        let mut res = resistor(5, Decimal::new(47, -2));
+        set_tolerance(&mut res, 100);
+        assert_eq!(res.bounds(), (466, 474));
+    }
+
+    #[test]
+    fn drift_bounds_widen_away_from_room_temperature() {
+        let mut res = resistor(6, Decimal::new(1, 3));
+        set_tolerance(&mut res, 100);
+        assert_eq!(res.drift_bounds(100, REFERENCE_TEMP), res.bounds());
+        // 100 ppm/K over 100K is another 1% either way
+        assert_eq!(res.drift_bounds(100, 125), (980_100, 1_020_100));
+        assert_eq!(res.drift_bounds(100, -75), (980_100, 1_020_100));
+    }
+
+    #[test]
+    fn from_preferred_uses_the_series_tolerance() {
+        let res = Resistance::from_preferred(Decimal::new(47, 2), Series::E24).unwrap();
+        assert_eq!(res.bands, 4);
+        assert_eq!(res.tolerance(), 500);
+        let res = Resistance::from_preferred(Decimal::new(102, 1), Series::E96).unwrap();
+        assert_eq!(res.bands, 5);
+        assert_eq!(res.ohms(), 1_020_000);
+    }
+
+    #[test]
+    fn capacitor_multiplier_range() {
+        // 47nF is 47 × 10^3 pF
+        let mut part = Resistance::with_component(Component::Capacitor, 4);
+        assert_eq!(part.set_decimal(Decimal::new(47, 3)), Ok(()));
+        assert_eq!(part.multiplier_pow.value, 3);
+        assert_eq!(
+            part.set_decimal(Decimal::new(47, 7)),
+            Err(ValueError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn inductor_multiplier_range() {
+        // 0.47µH uses the silver multiplier
+        let mut part = Resistance::with_component(Component::Inductor, 4);
+        assert_eq!(part.set_decimal(Decimal::new(47, -2)), Ok(()));
+        assert_eq!(part.multiplier_pow.value, -2);
+        assert_eq!(
+            part.set_decimal(Decimal::new(47, 4)),
+            Err(ValueError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn jumper_is_only_zero() {
+        let mut jumper = Resistance::jumper();
+        assert_eq!(jumper.set_decimal(Decimal::new(0, 0)), Ok(()));
+        assert_eq!(jumper.ohms(), 0);
+        assert_eq!(
+            jumper.set_decimal(Decimal::new(1, 0)),
+            Err(ValueError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn mil_layout_has_two_digits() {
+        let mut res = Resistance::mil();
+        assert_eq!(res.bands, 5);
+        assert_eq!(res.figures(), 2);
+        assert_eq!(
+            res.set_decimal(Decimal::new(471, 0)),
+            Err(ValueError::TooPrecise)
+        );
+        assert_eq!(res.set_decimal(Decimal::new(47, 2)), Ok(()));
+        assert_eq!(res.ohms(), 4_700_000);
+    }
+
+    #[test]
+    fn reversed_reading() {
+        // Yellow violet red gold starts with gold from the other end, which isn't a digit
+        let mut res = resistor(4, Decimal::new(47, 2));
+        set_tolerance(&mut res, 500);
+        assert!(res.reversed().is_none());
+        // Brown black black brown reads the same both ways
+        let mut res = resistor(4, Decimal::new(10, 0));
+        set_tolerance(&mut res, 100);
+        let reversed = res.reversed().unwrap();
+        assert_eq!(reversed.ohms(), 10_000);
+        assert_eq!(reversed.tolerance(), 100);
+        assert!(res.plausibility() > Resistance::new(4).plausibility());
+    }
+
+    #[test]
+    fn unexpected_series_for_the_tolerance() {
+        let mut res = resistor(4, Decimal::new(47, 2));
+        set_tolerance(&mut res, 500);
+        assert!(res.unexpected_series().is_none());
+        res.set_decimal(Decimal::new(5, 3)).unwrap();
+        assert!(res.unexpected_series() == Some(Series::E24));
+        // Tight tolerances are also made in E24 values
+        let mut res = resistor(5, Decimal::new(47, 2));
+        set_tolerance(&mut res, 100);
+        assert!(res.unexpected_series().is_none());
+    }
+
+    #[test]
+    fn fit_leaves_the_bands_alone() {
+        let res = resistor(4, Decimal::new(47, 2));
+        assert_eq!(res.fit(Decimal::new(1, 3)), Ok(Decimal::new(10, 2)));
+        assert_eq!(res.fit(Decimal::new(473, 0)), Err(ValueError::TooPrecise));
+        assert_eq!(res.ohms(), 4_700_000);
+        assert_eq!(
+            Resistance::jumper().fit(Decimal::new(1, 0)),
+            Err(ValueError::OutOfRange)
+        );
+    }
+}
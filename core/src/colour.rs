@@ -0,0 +1,55 @@
+// The colours bands can be, and the order each kind of band goes through them in
+
+// In the order their abbreviations are drawn in
+#[derive(Clone, Copy, PartialEq)]
+pub enum Colour {
+    Pink,
+    Silver,
+    Gold,
+    Black,
+    Brown,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Violet,
+    Gray,
+    White,
+}
+
+use Colour::*;
+
+pub const VALUE_COLORS: [Colour; 10] = [
+    Black, Brown, Red, Orange, Yellow, Green, Blue, Violet, Gray, White,
+];
+
+pub const MULTIPLIER_COLORS: [Colour; 13] = [
+    Pink, Silver, Gold, Black, Brown, Red, Orange, Yellow, Green, Blue, Violet, Gray, White,
+];
+
+pub const TOLERANCE_COLORS: [Colour; 10] = [
+    Gray, Yellow, Orange, Violet, Blue, Green, Brown, Red, Gold, Silver,
+];
+
+pub const TCR_COLORS: [Colour; 9] = [Gray, Violet, Blue, Orange, Green, Yellow, Red, Brown, Black];
+
+// Capacitor multipliers in pF, from silver (0.01) to blue (1M)
+pub const CAPACITOR_MULTIPLIER_COLORS: [Colour; 9] =
+    [Silver, Gold, Black, Brown, Red, Orange, Yellow, Green, Blue];
+pub const MIN_CAPACITOR_POW: i8 = -2;
+
+pub const CAPACITOR_TOLERANCE_COLORS: [Colour; 6] = [Black, Brown, Red, Orange, Green, White];
+
+pub const VOLTAGE_COLORS: [Colour; 4] = [Brown, Red, Yellow, Blue];
+
+pub const JUMPER_COLORS: [Colour; 1] = [Black];
+
+pub const FAILURE_RATE_COLORS: [Colour; 4] = [Brown, Red, Orange, Yellow];
+
+// Inductor multipliers in µH, from silver (0.01) to orange (1000)
+pub const INDUCTOR_MULTIPLIER_COLORS: [Colour; 6] = [Silver, Gold, Black, Brown, Red, Orange];
+pub const MIN_INDUCTOR_POW: i8 = -2;
+
+pub const INDUCTOR_TOLERANCE_COLORS: [Colour; 7] =
+    [Black, Brown, Red, Orange, Yellow, Gold, Silver];
@@ -0,0 +1,238 @@
+// Finding a combination of preferred values that gets closest to a target resistance
+
+use crate::eseries::*;
+use crate::value::*;
+
+pub const MAX_COMBINED: usize = 3;
+
+// Values tried for each part, keeping the search quick enough for the Arduboy
+// Triples try at most this many squared
+const MAX_CANDIDATES: u8 = 32;
+
+#[derive(Clone, Copy)]
+pub struct Combination {
+    pub parts: [Decimal; MAX_COMBINED],
+    pub count: u8,
+    pub parallel: bool,
+    pub total: Milliohms,
+}
+
+impl Combination {
+    // Error from the target in hundredths of a percent
+    pub fn error(&self, target: Milliohms) -> i32 {
+        ((self.total as i128 - target as i128) * 10000 / target as i128) as i32
+    }
+}
+
+// Best combination of `count` parts, trying both series and parallel
+pub fn find(target: Milliohms, series: Series, count: u8) -> Option<Combination> {
+    if target == 0 {
+        return None;
+    }
+
+    let mut best: Option<Combination> = None;
+    for parallel in [false, true] {
+        let found = if count >= 3 {
+            best_triple(target, series, parallel)
+        } else {
+            best_pair(target, series, parallel)
+        };
+        best = closer(best, found, target);
+    }
+    best
+}
+
+// Decade the lower resistor of a divider is picked from, 10k to 100k keeps the current low
+const DIVIDER_DECADE: i8 = 4;
+
+// Upper and lower preferred values whose divider output gets closest to the target
+// Voltages can be in any unit, as long as both are the same
+pub fn find_divider(input: u64, target: u64, series: Series) -> Option<(Decimal, Decimal)> {
+    if target == 0 || target >= input {
+        return None;
+    }
+
+    let mut best: Option<(Decimal, Decimal, u64)> = None;
+    for i in 0..series.len() {
+        let lower = Decimal::new(
+            series.get(i) as u32,
+            DIVIDER_DECADE + 1 - series.figures() as i8,
+        );
+        let lower_ohms = lower.milliohms();
+        // upper / lower = (input - output) / output
+        let ideal = lower_ohms as u128 * (input - target) as u128 / target as u128;
+        let upper = match candidate(series, ideal.min(Milliohms::MAX as u128) as Milliohms) {
+            Some(upper) => upper,
+            None => continue,
+        };
+
+        let error = divider_output(input, upper.milliohms(), lower_ohms).abs_diff(target);
+        if !matches!(best, Some((_, _, best_error)) if error >= best_error) {
+            best = Some((upper, lower, error));
+        }
+    }
+
+    best.map(|(upper, lower, _)| (upper, lower))
+}
+
+fn combine(a: Milliohms, b: Milliohms, parallel: bool) -> Milliohms {
+    if parallel {
+        crate::value::parallel(a, b)
+    } else {
+        a + b
+    }
+}
+
+fn closer(
+    a: Option<Combination>,
+    b: Option<Combination>,
+    target: Milliohms,
+) -> Option<Combination> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            if b.total.abs_diff(target) < a.total.abs_diff(target) {
+                Some(b)
+            } else {
+                Some(a)
+            }
+        }
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+// Nearest preferred value that can also be shown with colour bands
+fn candidate(series: Series, milliohms: Milliohms) -> Option<Decimal> {
+    if milliohms == 0 {
+        return None;
+    }
+    series
+        .nearest(Decimal::from_milliohms(milliohms))?
+        .fit(series.figures())
+        .ok()
+}
+
+// What the other part(s) need to be to reach the target, if anything can
+fn remainder(target: Milliohms, part: Milliohms, parallel: bool) -> Option<Milliohms> {
+    if parallel {
+        // 1/rest = 1/target - 1/part
+        if part <= target {
+            return None;
+        }
+        Some((part as u128 * target as u128 / (part - target) as u128) as Milliohms)
+    } else if part < target {
+        Some(target - part)
+    } else {
+        None
+    }
+}
+
+// Candidates for the first part, starting at an even split and moving towards the target
+// In series the first part is the largest, in parallel it's the smallest
+fn first_parts(
+    target: Milliohms,
+    series: Series,
+    parallel: bool,
+    split: u8,
+) -> impl Iterator<Item = Decimal> {
+    let start = if parallel {
+        target * split as Milliohms
+    } else {
+        target / split as Milliohms
+    };
+    let mut next = candidate(series, start);
+
+    (0..MAX_CANDIDATES).map_while(move |_| {
+        let part = next?;
+        next = if parallel {
+            series.below(part)
+        } else {
+            series.above(part)
+        }
+        .and_then(|stepped| stepped.fit(series.figures()).ok());
+        Some(part)
+    })
+}
+
+fn best_pair(target: Milliohms, series: Series, parallel: bool) -> Option<Combination> {
+    let mut best = None;
+
+    for a in first_parts(target, series, parallel, 2) {
+        let a_ohms = a.milliohms();
+        let rest = match remainder(target, a_ohms, parallel) {
+            Some(rest) => rest,
+            None => break,
+        };
+        if let Some(b) = candidate(series, rest) {
+            let found = Combination {
+                parts: [a, b, Decimal::new(0, 0)],
+                count: 2,
+                parallel,
+                total: combine(a_ohms, b.milliohms(), parallel),
+            };
+            best = closer(best, Some(found), target);
+        }
+    }
+
+    best
+}
+
+fn best_triple(target: Milliohms, series: Series, parallel: bool) -> Option<Combination> {
+    let mut best = None;
+
+    for a in first_parts(target, series, parallel, 3) {
+        let a_ohms = a.milliohms();
+        let rest = match remainder(target, a_ohms, parallel) {
+            Some(rest) => rest,
+            None => break,
+        };
+        if let Some(pair) = best_pair(rest, series, parallel) {
+            let found = Combination {
+                parts: [a, pair.parts[0], pair.parts[1]],
+                count: 3,
+                parallel,
+                total: combine(a_ohms, pair.total, parallel),
+            };
+            best = closer(best, Some(found), target);
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_an_exact_combination() {
+        let found = find(2_000_000, Series::E12, 2).unwrap();
+        assert_eq!(found.total, 2_000_000);
+        assert_eq!(found.error(2_000_000), 0);
+        assert_eq!(found.count, 2);
+
+        let found = find(3_000_000, Series::E3, 3).unwrap();
+        assert_eq!(found.total, 3_000_000);
+        assert_eq!(found.count, 3);
+    }
+
+    #[test]
+    fn find_gets_close() {
+        let target = 1_234_000;
+        let found = find(target, Series::E12, 2).unwrap();
+        assert!(found.error(target).abs() < 100);
+        assert_eq!(find(0, Series::E12, 2).map(|found| found.total), None);
+    }
+
+    #[test]
+    fn find_divider_output() {
+        let (upper, lower) = find_divider(5000, 3300, Series::E24).unwrap();
+        let output = divider_output(5000, upper.milliohms(), lower.milliohms());
+        assert!(output.abs_diff(3300) < 50);
+        assert!(Series::E24.contains(upper));
+        assert!(Series::E24.contains(lower));
+
+        assert_eq!(find_divider(5000, 5000, Series::E24), None);
+        assert_eq!(find_divider(5000, 0, Series::E24), None);
+    }
+}
@@ -0,0 +1,283 @@
+// Preferred number series from IEC 60063
+
+use crate::value::*;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Series {
+    E3,
+    E6,
+    E12,
+    E24,
+    E48,
+    E96,
+    E192,
+}
+
+pub const SERIES: [Series; 7] = [
+    Series::E3,
+    Series::E6,
+    Series::E12,
+    Series::E24,
+    Series::E48,
+    Series::E96,
+    Series::E192,
+];
+
+impl Series {
+    // A series is never empty
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(self) -> usize {
+        match self {
+            Series::E3 => 3,
+            Series::E6 => 6,
+            Series::E12 => 12,
+            Series::E24 => 24,
+            Series::E48 => 48,
+            Series::E96 => 96,
+            Series::E192 => 192,
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Series::E3 => "E3\0",
+            Series::E6 => "E6\0",
+            Series::E12 => "E12\0",
+            Series::E24 => "E24\0",
+            Series::E48 => "E48\0",
+            Series::E96 => "E96\0",
+            Series::E192 => "E192\0",
+        }
+    }
+
+    // Significant figures used by the series' values
+    pub const fn figures(self) -> u8 {
+        if self.len() <= E24.len() {
+            2
+        } else {
+            3
+        }
+    }
+
+    // Tolerance the series is made for, in hundredths of a percent
+    pub const fn tolerance(self) -> u16 {
+        match self {
+            Series::E3 | Series::E6 => 2000,
+            Series::E12 => 1000,
+            Series::E24 => 500,
+            Series::E48 => 200,
+            Series::E96 => 100,
+            Series::E192 => 50,
+        }
+    }
+
+    // Finest series made for parts of at least this tolerance
+    pub fn for_tolerance(tolerance: u16) -> Series {
+        SERIES
+            .iter()
+            .rev()
+            .copied()
+            .find(|series| series.tolerance() >= tolerance)
+            .unwrap_or(Series::E3)
+    }
+
+    // Significand of the nth value in a decade
+    // Each series is every few values of the next, apart from E24 and below which are historic
+    pub const fn get(self, i: usize) -> u16 {
+        if self.len() <= E24.len() {
+            E24[i * (E24.len() / self.len())] as u16
+        } else {
+            E192[i * (E192.len() / self.len())]
+        }
+    }
+
+    // Value scaled to three digits, so both table widths can be compared
+    const fn get_scaled(self, i: usize) -> u16 {
+        if self.figures() == 2 {
+            self.get(i) * 10
+        } else {
+            self.get(i)
+        }
+    }
+
+    const fn decimal(self, i: usize, decade: i8) -> Decimal {
+        Decimal::new(self.get(i) as u32, decade + 3 - self.figures() as i8)
+    }
+
+    // Index of the largest value in the decade that isn't above the scaled significand
+    fn floor_index(self, scaled: u16) -> usize {
+        // Binary search, as the combination finder calls this a lot
+        let (mut low, mut high) = (0, self.len() - 1);
+        while low < high {
+            // div_ceil is newer than the nightly the Arduboy is built with
+            #[allow(clippy::manual_div_ceil)]
+            let mid = (low + high + 1) / 2;
+            if self.get_scaled(mid) <= scaled {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+
+    pub fn contains(self, value: Decimal) -> bool {
+        if value.sig == 0 {
+            return false;
+        }
+        let (scaled, _, inexact) = split(value);
+        !inexact && self.get_scaled(self.floor_index(scaled)) == scaled
+    }
+
+    // Index of a series value in its decade, with the power of ten its table value is multiplied by
+    pub fn position(self, value: Decimal) -> Option<(usize, i8)> {
+        if !self.contains(value) {
+            return None;
+        }
+        let (scaled, decade, _) = split(value);
+        Some((self.floor_index(scaled), decade + 3 - self.figures() as i8))
+    }
+
+    // Largest series value strictly below the given value
+    pub fn below(self, value: Decimal) -> Option<Decimal> {
+        if value.sig == 0 {
+            return None;
+        }
+        let (scaled, decade, inexact) = split(value);
+        let i = self.floor_index(scaled);
+
+        if inexact || self.get_scaled(i) < scaled {
+            Some(self.decimal(i, decade))
+        } else if i > 0 {
+            Some(self.decimal(i - 1, decade))
+        } else {
+            Some(self.decimal(self.len() - 1, decade - 1))
+        }
+    }
+
+    // Smallest series value strictly above the given value
+    pub fn above(self, value: Decimal) -> Option<Decimal> {
+        if value.sig == 0 {
+            return None;
+        }
+        let (scaled, decade, _) = split(value);
+        let i = self.floor_index(scaled);
+
+        if i + 1 < self.len() {
+            Some(self.decimal(i + 1, decade))
+        } else {
+            Some(self.decimal(0, decade + 1))
+        }
+    }
+
+    // Closest series value, judged by ratio rather than difference
+    pub fn nearest(self, value: Decimal) -> Option<Decimal> {
+        if self.contains(value) {
+            let (scaled, decade, _) = split(value);
+            return Some(self.decimal(self.floor_index(scaled), decade));
+        }
+        let below = self.below(value)?;
+        let above = self.above(value)?;
+
+        // Compare value / below against above / value, all scaled to the value's decade
+        let (scaled, decade, _) = split(value);
+        let (below_scaled, below_decade, _) = split(below);
+        let (above_scaled, above_decade, _) = split(above);
+        let scaled = scaled as u32 * 10;
+        let below_scaled = below_scaled as u32 * if below_decade < decade { 1 } else { 10 };
+        let above_scaled = above_scaled as u32 * if above_decade > decade { 100 } else { 10 };
+
+        if scaled * scaled < below_scaled * above_scaled {
+            Some(below)
+        } else {
+            Some(above)
+        }
+    }
+}
+
+// Splits a non-zero value into a significand from 100 to 999 and its power of ten
+// Also returns whether digits had to be dropped to fit
+// is_multiple_of is newer than the nightly the Arduboy is built with
+#[allow(clippy::manual_is_multiple_of)]
+fn split(value: Decimal) -> (u16, i8, bool) {
+    let norm = value.normalise();
+    let len = count_digits(norm.sig);
+    if len <= 3 {
+        let pad = 3 - len;
+        (
+            (norm.sig * 10u32.pow(pad as u32)) as u16,
+            norm.pow - pad as i8,
+            false,
+        )
+    } else {
+        let div = 10u32.pow((len - 3) as u32);
+        (
+            (norm.sig / div) as u16,
+            norm.pow + (len - 3) as i8,
+            norm.sig % div != 0,
+        )
+    }
+}
+
+const E24: [u8; 24] = [
+    10, 11, 12, 13, 15, 16, 18, 20, 22, 24, 27, 30, 33, 36, 39, 43, 47, 51, 56, 62, 68, 75, 82, 91,
+];
+
+const E192: [u16; 192] = [
+    100, 101, 102, 104, 105, 106, 107, 109, 110, 111, 113, 114, 115, 117, 118, 120, 121, 123, 124,
+    126, 127, 129, 130, 132, 133, 135, 137, 138, 140, 142, 143, 145, 147, 149, 150, 152, 154, 156,
+    158, 160, 162, 164, 165, 167, 169, 172, 174, 176, 178, 180, 182, 184, 187, 189, 191, 193, 196,
+    198, 200, 203, 205, 208, 210, 213, 215, 218, 221, 223, 226, 229, 232, 234, 237, 240, 243, 246,
+    249, 252, 255, 258, 261, 264, 267, 271, 274, 277, 280, 284, 287, 291, 294, 298, 301, 305, 309,
+    312, 316, 320, 324, 328, 332, 336, 340, 344, 348, 352, 357, 361, 365, 370, 374, 379, 383, 388,
+    392, 397, 402, 407, 412, 417, 422, 427, 432, 437, 442, 448, 453, 459, 464, 470, 475, 481, 487,
+    493, 499, 505, 511, 517, 523, 530, 536, 542, 549, 556, 562, 569, 576, 583, 590, 597, 604, 612,
+    619, 626, 634, 642, 649, 657, 665, 673, 681, 690, 698, 706, 715, 723, 732, 741, 750, 759, 768,
+    777, 787, 796, 806, 816, 825, 835, 845, 856, 866, 876, 887, 898, 909, 920, 931, 942, 953, 965,
+    976, 988,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ohms(value: Option<Decimal>) -> Option<Milliohms> {
+        value.map(|value| value.milliohms() / 1000)
+    }
+
+    #[test]
+    fn contains_preferred_values() {
+        assert!(Series::E12.contains(Decimal::new(47, 2)));
+        assert!(Series::E12.contains(Decimal::new(4700, 0)));
+        assert!(!Series::E12.contains(Decimal::new(51, 2)));
+        assert!(Series::E24.contains(Decimal::new(51, 2)));
+        assert!(Series::E96.contains(Decimal::new(102, 1)));
+        assert!(!Series::E12.contains(Decimal::new(0, 0)));
+    }
+
+    #[test]
+    fn below_and_above_step_across_decades() {
+        assert_eq!(ohms(Series::E12.below(Decimal::new(47, 2))), Some(3900));
+        assert_eq!(ohms(Series::E12.above(Decimal::new(47, 2))), Some(5600));
+        assert_eq!(ohms(Series::E12.below(Decimal::new(5, 3))), Some(4700));
+        assert_eq!(ohms(Series::E12.below(Decimal::new(1, 3))), Some(820));
+        assert_eq!(ohms(Series::E12.above(Decimal::new(82, 2))), Some(10000));
+        assert_eq!(Series::E12.below(Decimal::new(0, 0)), None);
+    }
+
+    #[test]
+    fn nearest_by_ratio() {
+        assert_eq!(ohms(Series::E12.nearest(Decimal::new(47, 2))), Some(4700));
+        assert_eq!(ohms(Series::E12.nearest(Decimal::new(5, 3))), Some(4700));
+        assert_eq!(ohms(Series::E12.nearest(Decimal::new(53, 2))), Some(5600));
+        assert_eq!(ohms(Series::E3.nearest(Decimal::new(9, 3))), Some(10000));
+        assert_eq!(ohms(Series::E96.nearest(Decimal::new(1, 4))), Some(10000));
+    }
+
+    #[test]
+    fn for_tolerance_picks_the_series() {
+        assert!(Series::for_tolerance(500) == Series::E24);
+        assert!(Series::for_tolerance(100) == Series::E96);
+        assert!(Series::for_tolerance(2000) == Series::E6);
+    }
+}
@@ -0,0 +1,365 @@
+// Engineering notation for values, e.g. 4.7k, 12.0M or 0.47, worked out without floats
+
+use crate::value::*;
+
+// Index of the unit itself in a table of prefixes, which starts at a thousandth of it
+pub const UNIT_PREFIX: usize = 1;
+
+// Room for the longest u64 with a point, a prefix and the null
+const FORMATTED_LEN: usize = 24;
+
+// Lowest multiplier written as a fraction of the unit rather than with a prefix, as on the gold
+// and silver bands
+// Only for tables where the unit has no prefix, as parts kept in µH or pF still go down to n or f
+const FRACTION_POW: i8 = -2;
+
+// A value written out as text, with the null so it can be printed
+#[derive(Clone, Copy)]
+pub struct Formatted {
+    chars: [u8; FORMATTED_LEN],
+    len: usize,
+    prefix: Option<&'static str>,
+}
+
+impl Formatted {
+    // Writes the value with the largest prefix that keeps at least one whole unit
+    // Only the decimals that the significand actually gives are written, so 120 × 10^5 is 12.0M
+    pub fn new(value: Decimal, prefixes: &'static [&'static str; 5]) -> Self {
+        let exponent = if value.sig == 0 {
+            0
+        } else {
+            let lead = count_digits(value.sig) as i16 - 1 + value.pow as i16;
+            let bare_unit = prefixes[UNIT_PREFIX].starts_with(' ');
+            if lead < 0 && value.pow >= FRACTION_POW && bare_unit {
+                0
+            } else {
+                let min = -3 * UNIT_PREFIX as i16;
+                let max = 3 * (prefixes.len() - 1 - UNIT_PREFIX) as i16;
+                (lead.div_euclid(3) * 3).clamp(min, max)
+            }
+        };
+        Formatted::with_exponent(value, prefixes, exponent)
+    }
+
+    // Writes the value as a multiple of 10^exponent, using the prefix for it
+    fn with_exponent(value: Decimal, prefixes: &'static [&'static str; 5], exponent: i16) -> Self {
+        let mut formatted = Formatted {
+            chars: [0; FORMATTED_LEN],
+            len: 0,
+            prefix: None,
+        };
+
+        let shift = value.pow as i16 - exponent;
+        if value.sig == 0 {
+            formatted.push(b'0');
+        } else if shift >= 0 {
+            formatted.push_digits(value.sig as u64 * 10u64.pow(shift as u32), 1);
+        } else {
+            let decimals = (-shift) as u32;
+            let div = 10u64.pow(decimals);
+            formatted.push_digits(value.sig as u64 / div, 1);
+            formatted.push(b'.');
+            formatted.push_digits(value.sig as u64 % div, decimals);
+        }
+
+        let prefix = prefixes[(exponent / 3 + UNIT_PREFIX as i16) as usize];
+        if !prefix.starts_with(' ') {
+            formatted.prefix = Some(prefix);
+            formatted.push(prefix.as_bytes()[0]);
+        }
+        formatted
+    }
+
+    // RKM notation from IEC 60062, with the prefix or R in place of the decimal point, e.g. 4k7
+    pub fn rkm(value: Decimal, prefixes: &'static [&'static str; 5]) -> Self {
+        let mut decimal = Formatted::new(value, prefixes);
+        // Anything below the unit is written with R, so 0.047 is R047 rather than 47m
+        let bare_unit = prefixes[UNIT_PREFIX].starts_with(' ');
+        if bare_unit && decimal.prefix == Some(prefixes[UNIT_PREFIX - 1]) {
+            decimal = Formatted::with_exponent(value, prefixes, 0);
+        }
+        let letter = decimal.prefix.map_or(b'R', |prefix| prefix.as_bytes()[0]);
+        let number = decimal.without_prefix();
+        let number = &number.chars[..number.len];
+
+        let mut rkm = Formatted {
+            chars: [0; FORMATTED_LEN],
+            len: 0,
+            prefix: None,
+        };
+        match number.iter().position(|&c| c == b'.') {
+            Some(point) => {
+                // A leading zero is dropped, so 0.47 is R47
+                if &number[..point] != b"0" {
+                    rkm.push_all(&number[..point]);
+                }
+                rkm.push(letter);
+                rkm.push_all(&number[point + 1..]);
+            }
+            None => {
+                rkm.push_all(number);
+                rkm.push(letter);
+            }
+        }
+        rkm
+    }
+
+    fn push_all(&mut self, chars: &[u8]) {
+        for &c in chars {
+            self.push(c);
+        }
+    }
+
+    fn push(&mut self, c: u8) {
+        if self.len < FORMATTED_LEN - 1 {
+            self.chars[self.len] = c;
+            self.len += 1;
+        }
+    }
+
+    // Writes a number padded with zeros to at least `width` digits
+    fn push_digits(&mut self, n: u64, width: u32) {
+        let mut place = 1;
+        let mut digits = 1;
+        while n / place >= 10 || digits < width {
+            place *= 10;
+            digits += 1;
+        }
+        while place > 0 {
+            self.push(b'0' + (n / place % 10) as u8);
+            place /= 10;
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.chars[..=self.len]).unwrap_or("\0")
+    }
+
+    // Number of characters, including the prefix, so never empty
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    // The prefix, unless the value is in the unit itself
+    pub fn prefix(&self) -> Option<&'static str> {
+        self.prefix
+    }
+
+    // Just the number, for when the prefix is drawn separately
+    pub fn without_prefix(&self) -> Self {
+        let mut number = *self;
+        if self.prefix.is_some() {
+            number.len -= 1;
+            number.chars[number.len] = 0;
+            number.prefix = None;
+        }
+        number
+    }
+}
+
+// Reads RKM notation, e.g. 4k7, R47 or 2M2, giving the value and any tolerance letter after it
+// The first letter is always the decimal point, so 10kM is 10kΩ ±20% rather than anything mega
+// Prefixes can be typed in either case where that can't be mistaken for another, so 4K7 is 4k7
+pub fn parse_rkm(text: &[u8], prefixes: &[&str; 5]) -> Result<(Decimal, Option<u8>), ValueError> {
+    let start = text.iter().position(|&c| c != b' ').unwrap_or(text.len());
+    let end = text
+        .iter()
+        .rposition(|&c| c != b' ')
+        .map_or(start, |end| end + 1);
+    let text = &text[start..end];
+
+    let point = text
+        .iter()
+        .position(|&c| !c.is_ascii_digit())
+        .ok_or(ValueError::Malformed)?;
+    let pow = if text[point] == b'R' {
+        0
+    } else {
+        let letter = text[point];
+        let exact = prefixes
+            .iter()
+            .position(|prefix| !prefix.starts_with(' ') && prefix.as_bytes()[0] == letter);
+        // The other case only counts when it can't be mistaken for another prefix, as m and M can
+        let either_case = |prefix: &&str| {
+            !prefix.starts_with(' ') && prefix.as_bytes()[0].eq_ignore_ascii_case(&letter)
+        };
+        let prefix = match exact {
+            Some(prefix) => prefix,
+            None if prefixes.iter().filter(|prefix| either_case(prefix)).count() == 1 => {
+                prefixes.iter().position(either_case).unwrap()
+            }
+            None => return Err(ValueError::Malformed),
+        };
+        3 * (prefix as i8 - UNIT_PREFIX as i8)
+    };
+
+    let after = &text[point + 1..];
+    let places = after
+        .iter()
+        .position(|&c| !c.is_ascii_digit())
+        .unwrap_or(after.len());
+    let tolerance = match &after[places..] {
+        [] => None,
+        [letter] => Some(*letter),
+        _ => return Err(ValueError::Malformed),
+    };
+    if point + places == 0 {
+        return Err(ValueError::Malformed);
+    }
+
+    let mut sig: u32 = 0;
+    for &c in text[..point].iter().chain(after[..places].iter()) {
+        sig = sig
+            .checked_mul(10)
+            .and_then(|sig| sig.checked_add((c - b'0') as u32))
+            .ok_or(ValueError::TooPrecise)?;
+    }
+    Ok((Decimal::new(sig, pow - places as i8), tolerance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::*;
+
+    #[test]
+    fn new_uses_the_largest_prefix() {
+        assert_eq!(
+            Formatted::new(Decimal::new(47, 2), &PREFIXES).as_str(),
+            "4.7k\0"
+        );
+        assert_eq!(
+            Formatted::new(Decimal::new(120, 5), &PREFIXES).as_str(),
+            "12.0M\0"
+        );
+        assert_eq!(
+            Formatted::new(Decimal::new(0, 3), &PREFIXES).as_str(),
+            "0\0"
+        );
+    }
+
+    #[test]
+    fn new_writes_fractions_of_a_bare_unit() {
+        assert_eq!(
+            Formatted::new(Decimal::new(47, -2), &PREFIXES).as_str(),
+            "0.47\0"
+        );
+        assert_eq!(
+            Formatted::new(Decimal::new(100, -3), &PREFIXES).as_str(),
+            "100m\0"
+        );
+    }
+
+    #[test]
+    fn prefix_is_kept_apart() {
+        let formatted = Formatted::new(Decimal::new(47, 2), &PREFIXES);
+        assert_eq!(formatted.prefix(), Some("k\0"));
+        assert_eq!(formatted.len(), 4);
+        assert_eq!(formatted.without_prefix().as_str(), "4.7\0");
+        assert_eq!(
+            Formatted::new(Decimal::new(47, -2), &PREFIXES).prefix(),
+            None
+        );
+    }
+
+    #[test]
+    fn new_goes_below_a_prefixed_unit() {
+        // µH has no bare unit, so 0.47µH still goes down to n
+        assert_eq!(
+            Formatted::new(Decimal::new(47, -2), &INDUCTOR_PREFIXES).as_str(),
+            "470n\0"
+        );
+    }
+
+    #[test]
+    fn rkm_puts_the_letter_at_the_point() {
+        assert_eq!(
+            Formatted::rkm(Decimal::new(47, 2), &PREFIXES).as_str(),
+            "4k7\0"
+        );
+        assert_eq!(
+            Formatted::rkm(Decimal::new(47, -2), &PREFIXES).as_str(),
+            "R47\0"
+        );
+        assert_eq!(
+            Formatted::rkm(Decimal::new(120, 5), &PREFIXES).as_str(),
+            "12M0\0"
+        );
+        assert_eq!(
+            Formatted::rkm(Decimal::new(1, 2), &PREFIXES).as_str(),
+            "100R\0"
+        );
+    }
+
+    #[test]
+    fn rkm_writes_every_fraction_with_r() {
+        // 5 bands give these a third digit, which would otherwise put them in milliohms
+        assert_eq!(
+            Formatted::rkm(Decimal::new(470, -3), &PREFIXES).as_str(),
+            "R470\0"
+        );
+        assert_eq!(
+            Formatted::rkm(Decimal::new(330, -3), &PREFIXES).as_str(),
+            "R330\0"
+        );
+        assert_eq!(
+            Formatted::rkm(Decimal::new(47, -4), &PREFIXES).as_str(),
+            "R0047\0"
+        );
+    }
+
+    #[test]
+    fn parse_rkm_reads_the_value_and_tolerance() {
+        assert_eq!(
+            parse_rkm(b"4k7", &PREFIXES),
+            Ok((Decimal::new(47, 2), None))
+        );
+        assert_eq!(
+            parse_rkm(b"R47", &PREFIXES),
+            Ok((Decimal::new(47, -2), None))
+        );
+        assert_eq!(
+            parse_rkm(b" 2M2J ", &PREFIXES),
+            Ok((Decimal::new(22, 5), Some(b'J')))
+        );
+        // Only the first letter is the point
+        assert_eq!(
+            parse_rkm(b"10kM", &PREFIXES),
+            Ok((Decimal::new(10, 3), Some(b'M')))
+        );
+    }
+
+    #[test]
+    fn parse_rkm_takes_either_case_of_a_prefix() {
+        assert_eq!(
+            parse_rkm(b"4K7", &PREFIXES),
+            Ok((Decimal::new(47, 2), None))
+        );
+        assert_eq!(
+            parse_rkm(b"4K7K", &PREFIXES),
+            Ok((Decimal::new(47, 2), Some(b'K')))
+        );
+        // m and M are both prefixes, so they keep their own meaning
+        assert_eq!(
+            parse_rkm(b"4M7", &PREFIXES),
+            Ok((Decimal::new(47, 5), None))
+        );
+        assert_eq!(
+            parse_rkm(b"4m7", &PREFIXES),
+            Ok((Decimal::new(47, -4), None))
+        );
+    }
+
+    #[test]
+    fn parse_rkm_rejects_malformed_text() {
+        assert_eq!(parse_rkm(b"47", &PREFIXES), Err(ValueError::Malformed));
+        assert_eq!(parse_rkm(b"k", &PREFIXES), Err(ValueError::Malformed));
+        assert_eq!(parse_rkm(b"4x7", &PREFIXES), Err(ValueError::Malformed));
+        assert_eq!(parse_rkm(b"4k7JJ", &PREFIXES), Err(ValueError::Malformed));
+        assert_eq!(
+            parse_rkm(b"99999999999R", &PREFIXES),
+            Err(ValueError::TooPrecise)
+        );
+    }
+}
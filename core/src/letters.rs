@@ -0,0 +1,68 @@
+// Letter codes that parts lists and printed markings use in place of tolerance and TCR colours
+
+use crate::band::ValType;
+
+// IEC 60062 letters for each tolerance, as used after RKM codes, e.g. 4k7J
+pub const TOLERANCE_LETTERS: [&str; 10] = [
+    "L\0", "P\0", "W\0", "B\0", "C\0", "D\0", "F\0", "G\0", "J\0", "K\0",
+];
+pub const NO_BAND_TOLERANCE_LETTER: &str = "M\0";
+
+// IEC 60062 letters for each TCR, where 10 and 20 ppm/K share Z
+pub const TCR_LETTERS: [&str; 9] = [
+    "K\0", "M\0", "Z\0", "P\0", "Z\0", "Q\0", "R\0", "S\0", "U\0",
+];
+
+// Letters used for capacitor tolerances in printed codes, e.g. 104K
+pub const CAPACITOR_TOLERANCE_LETTERS: [&str; 6] = ["M\0", "F\0", "G\0", "H\0", "J\0", "K\0"];
+
+pub const FAILURE_RATE_LETTERS: [&str; 4] = ["M\0", "P\0", "R\0", "S\0"];
+
+// Letters for each colour a band can be, in the same order as its colour table
+// Inductor tolerances are left out as 4% doesn't have one
+pub fn letters(vtype: &ValType) -> Option<&'static [&'static str]> {
+    match vtype {
+        ValType::Tolerance => Some(&TOLERANCE_LETTERS),
+        ValType::TCR => Some(&TCR_LETTERS),
+        ValType::CapTolerance => Some(&CAPACITOR_TOLERANCE_LETTERS),
+        ValType::FailureRate => Some(&FAILURE_RATE_LETTERS),
+        _ => None,
+    }
+}
+
+// Index into the band's colour table for a letter, the tighter value winning where two share one
+pub fn colour_from_letter(vtype: &ValType, letter: u8) -> Option<usize> {
+    letters(vtype)?
+        .iter()
+        .position(|code| code.as_bytes()[0] == letter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::band::Band;
+
+    #[test]
+    fn colour_from_letter_finds_the_band() {
+        assert_eq!(colour_from_letter(&ValType::Tolerance, b'J'), Some(8));
+        assert_eq!(colour_from_letter(&ValType::Tolerance, b'K'), Some(9));
+        assert_eq!(colour_from_letter(&ValType::CapTolerance, b'K'), Some(5));
+        // 10 and 20 ppm/K share Z, so the tighter is picked
+        assert_eq!(colour_from_letter(&ValType::TCR, b'Z'), Some(2));
+        assert_eq!(colour_from_letter(&ValType::Tolerance, b'X'), None);
+        assert_eq!(colour_from_letter(&ValType::InductorTolerance, b'J'), None);
+    }
+
+    #[test]
+    fn letters_match_the_colour_tables() {
+        for vtype in [
+            ValType::Tolerance,
+            ValType::TCR,
+            ValType::CapTolerance,
+            ValType::FailureRate,
+        ] {
+            let letters = letters(&vtype).unwrap();
+            assert_eq!(letters.len(), Band::colours_from_valtype(&vtype).len());
+        }
+    }
+}
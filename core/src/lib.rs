@@ -0,0 +1,15 @@
+// The colour code itself, with nothing tied to the Arduboy, so any frontend can decode bands
+// Text in the tables ends with a null, so the Arduboy can print it as it is
+
+#![no_std]
+#![allow(non_upper_case_globals)]
+
+pub mod band;
+pub mod colour;
+pub mod combination;
+pub mod eseries;
+pub mod format;
+pub mod letters;
+pub mod smd;
+pub mod tables;
+pub mod value;
@@ -0,0 +1,290 @@
+// Surface mount resistor markings, converted to and from the same decimals as the colour bands
+
+use crate::eseries::*;
+use crate::value::*;
+
+pub const CODE_LEN: usize = 4;
+
+// Multiplier letters of EIA-96 codes, from 0.001 up to 100k
+// R is left out as it reads as a decimal point, Y means the same
+const EIA96_LETTERS: [u8; 9] = [b'Z', b'Y', b'X', b'A', b'B', b'C', b'D', b'E', b'F'];
+const EIA96_MIN_POW: i8 = -3;
+// Older alternatives for some of the letters, only used when reading
+// R is also read as Y, but only when asked for, as it's usually the decimal point
+const EIA96_ALIASES: [(u8, u8); 3] = [(b'S', b'X'), (b'H', b'B'), (b'R', b'Y')];
+
+// Ways of marking the value on the part
+#[derive(Clone, Copy, PartialEq)]
+pub enum Marking {
+    ThreeDigit,
+    FourDigit,
+    Eia96,
+}
+
+pub const MARKINGS: [Marking; 3] = [Marking::ThreeDigit, Marking::FourDigit, Marking::Eia96];
+
+// A marking as text, padded with a null so it can be printed
+#[derive(Clone, Copy)]
+pub struct Code {
+    chars: [u8; CODE_LEN + 1],
+}
+
+impl Code {
+    pub const fn new(chars: [u8; CODE_LEN]) -> Self {
+        Code {
+            chars: [chars[0], chars[1], chars[2], chars[3], 0],
+        }
+    }
+
+    pub fn get(&self, i: usize) -> u8 {
+        self.chars[i]
+    }
+
+    pub fn set(&mut self, i: usize, c: u8) {
+        self.chars[i] = c;
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.chars).unwrap_or("\0")
+    }
+
+    // The marking without surrounding spaces
+    fn trimmed(&self) -> &[u8] {
+        let chars = &self.chars[..CODE_LEN];
+        let start = chars.iter().position(|&c| c != b' ').unwrap_or(CODE_LEN);
+        let end = chars
+            .iter()
+            .rposition(|&c| c != b' ')
+            .map_or(start, |end| end + 1);
+        &chars[start..end]
+    }
+
+    // Reads any kind of marking, working out which it is from its characters
+    pub fn decode(&self) -> Result<Decimal, ValueError> {
+        let chars = self.trimmed();
+        if chars.contains(&b'R') {
+            decode_point(chars)
+        } else if chars.len() == 3 && chars[2].is_ascii_uppercase() {
+            decode_eia96(chars)
+        } else if chars.len() == 3 || chars.len() == 4 {
+            // The last digit is the number of zeros
+            let sig = digits(&chars[..chars.len() - 1])?;
+            let pow = digits(&chars[chars.len() - 1..])?;
+            Ok(Decimal::new(sig, pow as i8))
+        } else {
+            Err(ValueError::Malformed)
+        }
+    }
+
+    // The EIA-96 reading of two digits then R, e.g. 1.02Ω for 02R, which decode reads as 2Ω
+    // Both are used, so the marking alone can't say which is meant
+    pub fn eia96_reading(&self) -> Option<Decimal> {
+        match self.trimmed() {
+            chars @ [first, second, b'R'] if first.is_ascii_digit() && second.is_ascii_digit() => {
+                decode_eia96(chars).ok()
+            }
+            _ => None,
+        }
+    }
+
+    // Writes a value as a marking, R takes the place of the decimal point for small values
+    pub fn encode(value: Decimal, marking: Marking) -> Result<Self, ValueError> {
+        match marking {
+            Marking::ThreeDigit => encode_digits(value, 2),
+            Marking::FourDigit => encode_digits(value, 3),
+            Marking::Eia96 => encode_eia96(value),
+        }
+    }
+}
+
+// Reads a run of digits as a number
+fn digits(chars: &[u8]) -> Result<u32, ValueError> {
+    if chars.is_empty() {
+        return Err(ValueError::Malformed);
+    }
+    let mut n = 0;
+    for &c in chars {
+        if !c.is_ascii_digit() {
+            return Err(ValueError::Malformed);
+        }
+        n = n * 10 + (c - b'0') as u32;
+    }
+    Ok(n)
+}
+
+// R notation, e.g. 4R7 or R47
+fn decode_point(chars: &[u8]) -> Result<Decimal, ValueError> {
+    let point = chars
+        .iter()
+        .position(|&c| c == b'R')
+        .ok_or(ValueError::Malformed)?;
+    let before = &chars[..point];
+    let after = &chars[point + 1..];
+    if before.len() + after.len() == 0 {
+        return Err(ValueError::Malformed);
+    }
+
+    // Either side may be empty, but not both, and there's only one R
+    let whole = if before.is_empty() {
+        0
+    } else {
+        digits(before)?
+    };
+    let fraction = if after.is_empty() { 0 } else { digits(after)? };
+    let places = after.len() as u32;
+    Ok(Decimal::new(
+        whole * 10u32.pow(places) + fraction,
+        -(places as i8),
+    ))
+}
+
+// The index of an E96 value followed by a multiplier letter, e.g. 01C for 10kΩ
+fn decode_eia96(chars: &[u8]) -> Result<Decimal, ValueError> {
+    let index = digits(&chars[..2])? as usize;
+    if index < 1 || index > Series::E96.len() {
+        return Err(ValueError::OutOfRange);
+    }
+
+    let letter = EIA96_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == chars[2])
+        .map_or(chars[2], |(_, letter)| *letter);
+    let pow = EIA96_LETTERS
+        .iter()
+        .position(|&c| c == letter)
+        .ok_or(ValueError::Malformed)? as i8
+        + EIA96_MIN_POW;
+
+    Ok(Decimal::new(Series::E96.get(index - 1) as u32, pow))
+}
+
+// Significant figures followed by the number of zeros
+// Values that need a multiplier below one put an R in place of the decimal point instead
+fn encode_digits(value: Decimal, figures: u8) -> Result<Code, ValueError> {
+    let value = value.normalise();
+    let len = count_digits(value.sig);
+    if len > figures {
+        return Err(ValueError::TooPrecise);
+    }
+
+    // Every figure is always written, taking zeros off the multiplier
+    let pad = if value.sig == 0 { 0 } else { figures - len };
+    let sig = value.sig * 10u32.pow(pad as u32);
+    let pow = value.pow as i16 - pad as i16;
+
+    let mut code = Code::new([b' '; CODE_LEN]);
+    write_digits(&mut code, sig, figures as usize);
+    if pow >= 0 {
+        if pow > 9 {
+            return Err(ValueError::OutOfRange);
+        }
+        code.set(figures as usize, b'0' + pow as u8);
+    } else {
+        let places = (-pow) as u8;
+        if places > figures {
+            return Err(ValueError::OutOfRange);
+        }
+        // Shift the fraction along to make room for the R
+        let whole = (figures - places) as usize;
+        for i in (whole..figures as usize).rev() {
+            code.set(i + 1, code.get(i));
+        }
+        code.set(whole, b'R');
+    }
+    Ok(code)
+}
+
+fn write_digits(code: &mut Code, mut n: u32, len: usize) {
+    for i in (0..len).rev() {
+        code.set(i, b'0' + (n % 10) as u8);
+        n /= 10;
+    }
+}
+
+fn encode_eia96(value: Decimal) -> Result<Code, ValueError> {
+    let (index, pow) = Series::E96.position(value).ok_or(ValueError::TooPrecise)?;
+    if pow < EIA96_MIN_POW || pow >= EIA96_MIN_POW + EIA96_LETTERS.len() as i8 {
+        return Err(ValueError::OutOfRange);
+    }
+
+    let mut code = Code::new([b' '; CODE_LEN]);
+    write_digits(&mut code, index as u32 + 1, 2);
+    code.set(2, EIA96_LETTERS[(pow - EIA96_MIN_POW) as usize]);
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(text: &[u8; CODE_LEN]) -> Code {
+        Code::new(*text)
+    }
+
+    #[test]
+    fn decode_each_marking() {
+        assert_eq!(code(b"472 ").decode(), Ok(Decimal::new(47, 2)));
+        assert_eq!(code(b"4701").decode(), Ok(Decimal::new(470, 1)));
+        assert_eq!(code(b"4R7 ").decode(), Ok(Decimal::new(47, -1)));
+        assert_eq!(code(b" R47").decode(), Ok(Decimal::new(47, -2)));
+        assert_eq!(code(b"01C ").decode(), Ok(Decimal::new(100, 2)));
+        // Older letters for the same multipliers
+        assert_eq!(code(b"01H ").decode(), code(b"01B ").decode());
+    }
+
+    #[test]
+    fn decode_rejects_bad_markings() {
+        assert_eq!(code(b"4RR ").decode(), Err(ValueError::Malformed));
+        assert_eq!(code(b"47  ").decode(), Err(ValueError::Malformed));
+        assert_eq!(code(b"00C ").decode(), Err(ValueError::OutOfRange));
+        assert_eq!(code(b"97C ").decode(), Err(ValueError::OutOfRange));
+    }
+
+    #[test]
+    fn encode_each_marking() {
+        let encode = |value, marking| Code::encode(value, marking).map(|code| code.chars);
+        assert_eq!(
+            encode(Decimal::new(47, 2), Marking::ThreeDigit),
+            Ok(*b"472 \0")
+        );
+        assert_eq!(
+            encode(Decimal::new(47, 2), Marking::FourDigit),
+            Ok(*b"4701\0")
+        );
+        assert_eq!(
+            encode(Decimal::new(47, -1), Marking::ThreeDigit),
+            Ok(*b"4R7 \0")
+        );
+        assert_eq!(encode(Decimal::new(100, 2), Marking::Eia96), Ok(*b"01C \0"));
+        assert_eq!(
+            encode(Decimal::new(47, 2), Marking::Eia96),
+            Err(ValueError::TooPrecise)
+        );
+        assert_eq!(
+            encode(Decimal::new(473, 0), Marking::ThreeDigit),
+            Err(ValueError::TooPrecise)
+        );
+    }
+
+    #[test]
+    fn encode_then_decode() {
+        for value in [
+            Decimal::new(47, 2),
+            Decimal::new(1, -1),
+            Decimal::new(22, 6),
+        ] {
+            let code = Code::encode(value, Marking::ThreeDigit).unwrap();
+            assert_eq!(
+                code.decode().map(|value| value.milliohms()),
+                Ok(value.milliohms())
+            );
+        }
+    }
+
+    #[test]
+    fn two_digits_then_r_read_both_ways() {
+        assert_eq!(code(b"02R ").decode(), Ok(Decimal::new(2, 0)));
+        assert_eq!(code(b"02R ").eia96_reading(), Some(Decimal::new(102, -2)));
+        assert_eq!(code(b"4R7 ").eia96_reading(), None);
+    }
+}
@@ -0,0 +1,39 @@
+// What each band colour stands for, in the same order as the colour tables
+
+pub const TOLERANCES: [&str; 10] = [
+    "0.01\0", "0.02\0", "0.05\0", "0.10\0", "0.25\0", "0.50\0", "1.00\0", "2.00\0", "5.00\0",
+    "10.0\0",
+];
+// In hundredths of a percent, matching TOLERANCES
+pub const TOLERANCE_VALUES: [u16; 10] = [1, 2, 5, 10, 25, 50, 100, 200, 500, 1000];
+pub const NO_BAND_TOLERANCE: u16 = 2000;
+// Tolerances most parts are actually made in, which makes a reading using them likelier
+pub const COMMON_TOLERANCES: [u16; 4] = [100, 200, 500, 1000];
+
+pub const TCRs: [&str; 9] = [
+    "1\0", "5\0", "10\0", "15\0", "20\0", "25\0", "50\0", "100\0", "250\0",
+];
+
+// In ppm/K, matching TCRs
+pub const TCR_VALUES: [u16; 9] = [1, 5, 10, 15, 20, 25, 50, 100, 250];
+
+pub const CAPACITOR_TOLERANCES: [&str; 6] = ["20\0", "1\0", "2\0", "3\0", "5\0", "10\0"];
+// In hundredths of a percent, matching CAPACITOR_TOLERANCES
+pub const CAPACITOR_TOLERANCE_VALUES: [u16; 6] = [2000, 100, 200, 300, 500, 1000];
+
+pub const INDUCTOR_TOLERANCES: [&str; 7] = ["20\0", "1\0", "2\0", "3\0", "4\0", "5\0", "10\0"];
+// In hundredths of a percent, matching INDUCTOR_TOLERANCES
+pub const INDUCTOR_TOLERANCE_VALUES: [u16; 7] = [2000, 100, 200, 300, 400, 500, 1000];
+
+// Failure rate levels, in percent per 1000 hours
+pub const FAILURE_RATES: [&str; 4] = ["1\0", "0.1\0", ".01\0", ".001\0"];
+
+// Working voltage, matching VOLTAGE_COLORS
+pub const VOLTAGES: [&str; 4] = ["100\0", "250\0", "400\0", "630\0"];
+
+// Temperature that the nominal resistance is specified at, in °C
+pub const REFERENCE_TEMP: i16 = 25;
+
+pub const PREFIXES: [&str; 5] = ["m\0", " \0", "k\0", "M\0", "G\0"];
+pub const CAPACITOR_PREFIXES: [&str; 5] = ["f\0", "p\0", "n\0", "u\0", "m\0"];
+pub const INDUCTOR_PREFIXES: [&str; 5] = ["n\0", "u\0", "m\0", " \0", "k\0"];
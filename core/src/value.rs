@@ -0,0 +1,285 @@
+// Exact decimal values, stored the same way the colour code stores them
+
+// Fixed point resistance, so values like 0.47Ω stay exact without floats
+pub type Milliohms = u64;
+
+// Range of the multiplier band, from pink (0.001) to white (1G)
+pub const MIN_POW: i8 = -3;
+pub const MAX_POW: i8 = 9;
+
+// Why a value can't be shown on the bands
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum ValueError {
+    Malformed,
+    TooPrecise,
+    OutOfRange,
+}
+
+// Significant figures multiplied by a power of ten
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct Decimal {
+    pub sig: u32,
+    pub pow: i8,
+}
+
+impl Decimal {
+    pub const fn new(sig: u32, pow: i8) -> Self {
+        Decimal { sig, pow }
+    }
+
+    // Moves trailing zeros of the significand into the exponent
+    // is_multiple_of is newer than the nightly the Arduboy is built with
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn normalise(self) -> Self {
+        let mut out = self;
+        if out.sig == 0 {
+            return Decimal::new(0, 0);
+        }
+        while out.sig % 10 == 0 {
+            out.sig /= 10;
+            out.pow += 1;
+        }
+        out
+    }
+
+    // Rewrites the value with exactly `figures` digits and a multiplier in range
+    // Leading zero digits are only used when the multiplier can't go any lower
+    pub fn fit(self, figures: u8) -> Result<Self, ValueError> {
+        self.fit_within(figures, MIN_POW, MAX_POW)
+    }
+
+    // The same, for bands with a different multiplier range
+    pub fn fit_within(self, figures: u8, min_pow: i8, max_pow: i8) -> Result<Self, ValueError> {
+        let norm = self.normalise();
+        if norm.sig == 0 {
+            return Ok(norm);
+        }
+
+        let len = count_digits(norm.sig);
+        if len > figures {
+            return Err(ValueError::TooPrecise);
+        }
+
+        let mut pad = figures - len;
+        while pad > 0 && (norm.pow as i16 - pad as i16) < min_pow as i16 {
+            pad -= 1;
+        }
+
+        let pow = norm.pow as i16 - pad as i16;
+        if pow < min_pow as i16 || pow > max_pow as i16 {
+            return Err(ValueError::OutOfRange);
+        }
+
+        Ok(Decimal::new(norm.sig * 10u32.pow(pad as u32), pow as i8))
+    }
+
+    // Drops trailing digits past the given number of figures, rounding half up
+    pub fn round(self, figures: u8) -> Self {
+        let norm = self.normalise();
+        let len = count_digits(norm.sig);
+        if len <= figures {
+            return norm;
+        }
+        let div = 10u32.pow((len - figures) as u32);
+        Decimal::new((norm.sig + div / 2) / div, norm.pow + (len - figures) as i8).normalise()
+    }
+
+    pub fn from_milliohms(milliohms: Milliohms) -> Self {
+        Decimal::from_milli(milliohms)
+    }
+
+    pub fn milliohms(&self) -> Milliohms {
+        self.milli()
+    }
+
+    // Thousandths of the value, for quantities other than resistance
+    // Also waits on is_multiple_of, like normalise
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn from_milli(milli: u64) -> Self {
+        let mut sig = milli;
+        let mut pow = -3;
+        while sig > u32::MAX as u64 || (sig != 0 && sig % 10 == 0) {
+            sig /= 10;
+            pow += 1;
+        }
+        Decimal::new(sig as u32, pow)
+    }
+
+    pub fn milli(&self) -> u64 {
+        self.scaled(3)
+    }
+
+    pub fn micro(&self) -> u64 {
+        self.scaled(6)
+    }
+
+    // The value multiplied by 10^places, dropping anything after the point
+    fn scaled(&self, places: i8) -> u64 {
+        let pow = self.pow as i16 + places as i16;
+        if pow >= 0 {
+            self.sig as u64 * 10u64.pow(pow as u32)
+        } else {
+            self.sig as u64 / 10u64.pow((-pow) as u32)
+        }
+    }
+}
+
+// Equivalent of two resistances in parallel, a short in either gives a short
+pub fn parallel(a: Milliohms, b: Milliohms) -> Milliohms {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a as u128 * b as u128 / (a as u128 + b as u128)) as Milliohms
+}
+
+// Output of a voltage divider, in the same units as the input
+pub fn divider_output(input: u64, upper: Milliohms, lower: Milliohms) -> u64 {
+    if upper + lower == 0 {
+        return 0;
+    }
+    (input as u128 * lower as u128 / (upper as u128 + lower as u128)) as u64
+}
+
+// Works out value * ppm / 1,000,000 without overflowing for large resistances
+pub fn parts_per_million(value: Milliohms, ppm: u32) -> Milliohms {
+    let ppm = ppm as Milliohms;
+    value / 1_000_000 * ppm + value % 1_000_000 * ppm / 1_000_000
+}
+
+// Number of decimal digits, with zero having none
+pub fn count_digits(mut n: u32) -> u8 {
+    let mut count = 0;
+    while n > 0 {
+        n /= 10;
+        count += 1;
+    }
+    count
+}
+
+// Largest whole number whose square isn't above n
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method, starting above the root so it only ever falls
+    let mut root = n;
+    let mut next = n / 2 + n % 2;
+    while next < root {
+        root = next;
+        next = (root + n / root) / 2;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_pads_to_the_figures() {
+        // 4.7k on 2 and 3 digit bands
+        assert_eq!(Decimal::new(4700, 0).fit(2), Ok(Decimal::new(47, 2)));
+        assert_eq!(Decimal::new(47, 2).fit(3), Ok(Decimal::new(470, 1)));
+        assert_eq!(Decimal::new(0, 5).fit(2), Ok(Decimal::new(0, 0)));
+    }
+
+    #[test]
+    fn fit_uses_leading_zeros_only_at_the_lowest_multiplier() {
+        assert_eq!(Decimal::new(1, -3).fit(2), Ok(Decimal::new(1, -3)));
+        assert_eq!(Decimal::new(47, -3).fit(3), Ok(Decimal::new(47, -3)));
+    }
+
+    #[test]
+    fn fit_rejects_what_the_bands_cannot_show() {
+        assert_eq!(Decimal::new(473, 0).fit(2), Err(ValueError::TooPrecise));
+        assert_eq!(Decimal::new(1, -4).fit(2), Err(ValueError::OutOfRange));
+        assert_eq!(Decimal::new(1, 11).fit(2), Err(ValueError::OutOfRange));
+    }
+
+    #[test]
+    fn milliohms_of_the_value() {
+        assert_eq!(Decimal::new(47, -2).milliohms(), 470);
+        assert_eq!(Decimal::new(47, 2).milliohms(), 4_700_000);
+        assert_eq!(Decimal::new(1, -6).milliohms(), 0);
+    }
+
+    #[test]
+    fn round_half_up() {
+        assert_eq!(Decimal::new(4749, 0).round(2), Decimal::new(47, 2));
+        assert_eq!(Decimal::new(4750, 0).round(2), Decimal::new(48, 2));
+        // Carrying into another digit normalises it away
+        assert_eq!(Decimal::new(996, 0).round(2), Decimal::new(1, 3));
+        assert_eq!(Decimal::new(4700, 0).round(2), Decimal::new(47, 2));
+    }
+
+    #[test]
+    fn from_milliohms_drops_trailing_zeros() {
+        assert_eq!(Decimal::from_milliohms(470), Decimal::new(47, -2));
+        assert_eq!(Decimal::from_milliohms(4_700_000), Decimal::new(47, 2));
+        assert_eq!(Decimal::from_milliohms(0).milliohms(), 0);
+    }
+
+    #[test]
+    fn parts_per_million_without_overflow() {
+        assert_eq!(parts_per_million(4_700_000, 50_000), 235_000);
+        assert_eq!(parts_per_million(999, 1000), 0);
+        assert_eq!(parts_per_million(u64::MAX / 2, 1_000_000), u64::MAX / 2);
+    }
+
+    #[test]
+    fn parallel_resistance() {
+        assert_eq!(parallel(10_000, 10_000), 5_000);
+        assert_eq!(parallel(10_000, 40_000), 8_000);
+        assert_eq!(parallel(0, 10_000), 0);
+    }
+
+    #[test]
+    fn divider_output_splits_the_input() {
+        assert_eq!(divider_output(5000, 10_000, 10_000), 2500);
+        assert_eq!(divider_output(5000, 0, 10_000), 5000);
+        assert_eq!(divider_output(5000, 0, 0), 0);
+    }
+
+    #[test]
+    fn from_milli_for_other_quantities() {
+        assert_eq!(Decimal::from_milli(3300), Decimal::new(33, -1));
+        assert_eq!(Decimal::new(33, -1).milli(), 3300);
+    }
+
+    #[test]
+    fn micro_keeps_smaller_fractions() {
+        assert_eq!(Decimal::new(2, -2).micro(), 20_000);
+        assert_eq!(Decimal::new(15, 0).micro(), 15_000_000);
+    }
+
+    #[test]
+    fn fit_within_uses_the_bands_range() {
+        assert_eq!(
+            Decimal::new(47, 1).fit_within(2, -2, 0),
+            Err(ValueError::OutOfRange)
+        );
+        assert_eq!(
+            Decimal::new(47, 0).fit_within(2, -2, 0),
+            Ok(Decimal::new(47, 0))
+        );
+        // Padding stops at the lowest multiplier of the range
+        assert_eq!(
+            Decimal::new(1, -2).fit_within(2, -2, 6),
+            Ok(Decimal::new(1, -2))
+        );
+    }
+
+    #[test]
+    fn isqrt_rounds_down() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        let max = u32::MAX as u64;
+        assert_eq!(isqrt(max * max), max);
+        // Power saturates the product it takes the root of
+        assert_eq!(isqrt(u64::MAX), max);
+    }
+}
@@ -0,0 +1,45 @@
+// Runs the game without an Arduboy, reading the buttons held from stdin as a script
+// Each line is a run of frames, with the buttons held separated by spaces and then how many
+// frames if it's more than one, e.g. "UP DOWN B" or "RIGHT 3", as described in game::input
+// The screen after the last frame is printed along with the LED colour
+// With --record the frames are also printed back as a script, merged into runs
+
+use arduboy_mock::host;
+use game::input;
+use std::env;
+use std::io::{self, BufRead};
+
+fn main() {
+    let record = env::args().skip(1).any(|arg| arg == "--record");
+
+    host::reset();
+    unsafe { game::setup() };
+    if record {
+        unsafe { game::input() }.record();
+    }
+
+    for (number, line) in io::stdin().lock().lines().enumerate() {
+        let line = line.expect("Couldn't read stdin");
+        let (held, frames) = match input::parse_line(&line) {
+            Ok(run) => run,
+            Err(word) => {
+                eprintln!("{} on line {} isn't a button", word, number + 1);
+                std::process::exit(1);
+            }
+        };
+        host::hold(held);
+        for _ in 0..frames {
+            unsafe { game::loop_() };
+        }
+    }
+
+    print!("{}", host::screen().to_ascii());
+    let (red, green, blue) = host::led();
+    println!("LED {} {} {}", red, green, blue);
+
+    if record {
+        let mut script = String::new();
+        unsafe { game::input() }.write_recording(&mut script).unwrap();
+        print!("{}", script);
+    }
+}
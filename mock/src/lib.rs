@@ -0,0 +1,204 @@
+// A stand-in for the Rust for Arduboy library that draws into memory, so the game can run headless
+// The prelude matches the parts of the library the game uses, and host has what drives it
+
+mod font;
+pub mod prelude;
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+pub const WIDTH: usize = 128;
+pub const HEIGHT: usize = 64;
+// One bit per pixel, each byte being a column of 8 pixels as on the Arduboy
+pub const BUFFER_SIZE: usize = WIDTH * HEIGHT / 8;
+pub const EEPROM_SIZE: usize = 1024;
+// The Arduboy keeps its own settings at the start of the EEPROM
+pub const EEPROM_STORAGE_SPACE_START: usize = 16;
+
+const CHAR_WIDTH: i16 = 6;
+const CHAR_HEIGHT: i16 = 8;
+
+// Everything the game can change, shared by all the calls it makes
+struct State {
+    // Being drawn into
+    buffer: [u8; BUFFER_SIZE],
+    // What was there when display was last called
+    screen: [u8; BUFFER_SIZE],
+    cursor_x: i16,
+    cursor_y: i16,
+    led: (u8, u8, u8),
+    // Held at the last poll, and the poll before that
+    buttons: u8,
+    previous_buttons: u8,
+    // Held from the next poll on
+    held: u8,
+    eeprom: [u8; EEPROM_SIZE],
+    frame_rate: u8,
+    frames: u32,
+}
+
+impl State {
+    const fn new() -> Self {
+        State {
+            buffer: [0; BUFFER_SIZE],
+            screen: [0; BUFFER_SIZE],
+            cursor_x: 0,
+            cursor_y: 0,
+            led: (0, 0, 0),
+            buttons: 0,
+            previous_buttons: 0,
+            held: 0,
+            // Erased EEPROM reads as all ones
+            eeprom: [0xff; EEPROM_SIZE],
+            frame_rate: 60,
+            frames: 0,
+        }
+    }
+
+    fn draw_pixel(&mut self, x: i16, y: i16, white: bool) {
+        if x < 0 || y < 0 || x >= WIDTH as i16 || y >= HEIGHT as i16 {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let byte = &mut self.buffer[y / 8 * WIDTH + x];
+        if white {
+            *byte |= 1 << (y % 8);
+        } else {
+            *byte &= !(1 << (y % 8));
+        }
+    }
+
+    fn draw_char(&mut self, c: u8) {
+        match c {
+            b'\n' => {
+                self.cursor_x = 0;
+                self.cursor_y += CHAR_HEIGHT;
+                return;
+            }
+            b'\r' => return,
+            _ => {}
+        }
+
+        // White text on a black background, the gap after the character included
+        let glyph = font::glyph(c);
+        for column in 0..CHAR_WIDTH {
+            let bits = glyph.get(column as usize).copied().unwrap_or(0);
+            for row in 0..CHAR_HEIGHT {
+                self.draw_pixel(
+                    self.cursor_x + column,
+                    self.cursor_y + row,
+                    bits & (1 << row) != 0,
+                );
+            }
+        }
+        self.cursor_x += CHAR_WIDTH;
+    }
+
+    fn print_bytes(&mut self, bytes: &[u8]) {
+        // Strings end at their null, as they would when printed from C
+        for &c in bytes.iter().take_while(|&&c| c != 0) {
+            self.draw_char(c);
+        }
+    }
+}
+
+static STATE: Mutex<State> = Mutex::new(State::new());
+
+fn state() -> MutexGuard<'static, State> {
+    // A failed test shouldn't stop the rest from using the mock
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// For scripts and tests to drive the game and see what it did
+pub mod host {
+    use super::*;
+
+    // A copy of the screen as it was last displayed
+    #[derive(Clone, PartialEq)]
+    pub struct Screen {
+        buffer: [u8; BUFFER_SIZE],
+    }
+
+    impl Screen {
+        pub fn pixel(&self, x: usize, y: usize) -> bool {
+            self.buffer[y / 8 * WIDTH + x] & (1 << (y % 8)) != 0
+        }
+
+        // One line per row of pixels, # for lit and . for dark
+        pub fn to_ascii(&self) -> String {
+            let mut ascii = String::with_capacity((WIDTH + 1) * HEIGHT);
+            for y in 0..HEIGHT {
+                for x in 0..WIDTH {
+                    ascii.push(if self.pixel(x, y) { '#' } else { '.' });
+                }
+                ascii.push('\n');
+            }
+            ascii
+        }
+    }
+
+    // Back to how the Arduboy is when it's first turned on, with the EEPROM erased
+    pub fn reset() {
+        *state() = State::new();
+    }
+
+    // Buttons to hold down from the next poll, as the flags of the prelude's ButtonSets
+    pub fn hold(buttons: u8) {
+        state().held = buttons;
+    }
+
+    pub fn screen() -> Screen {
+        Screen {
+            buffer: state().screen,
+        }
+    }
+
+    pub fn led() -> (u8, u8, u8) {
+        state().led
+    }
+
+    pub fn eeprom() -> [u8; EEPROM_SIZE] {
+        state().eeprom
+    }
+
+    pub fn set_eeprom(address: usize, value: u8) {
+        state().eeprom[address] = value;
+    }
+
+    // Frames that have been displayed since the reset
+    pub fn frames() -> u32 {
+        state().frames
+    }
+
+    pub fn frame_rate() -> u8 {
+        state().frame_rate
+    }
+}
+
+// Sprites and strings are kept as ordinary statics, as there's no separate program memory
+#[macro_export]
+macro_rules! progmem {
+    (
+        $( #[$attr:meta] )*
+        $v:vis static $name:ident: [$ty:ty; $len:expr] = $value:expr;
+        $($rest:tt)*
+    ) => {
+        $v static $name: [$ty; $len] = $value;
+        $crate::progmem!{ $($rest)* }
+    };
+    () => ();
+}
+
+#[macro_export]
+macro_rules! get_sprite_addr {
+    ($s:expr) => {
+        unsafe { core::ptr::addr_of!($s) as *const u8 }
+    };
+}
+
+#[macro_export]
+macro_rules! f {
+    ($s:literal) => {{
+        static STRING: [u8; $s.len()] = *$s;
+        &STRING as &[u8]
+    }};
+}
@@ -0,0 +1,321 @@
+// The same names as the library's prelude, so the game only has to change which one it uses
+
+pub use crate::{f, get_sprite_addr, progmem};
+pub use core::ffi::c_char;
+
+use crate::{state, EEPROM_SIZE, EEPROM_STORAGE_SPACE_START};
+
+pub const WIDTH: u8 = crate::WIDTH as u8;
+pub const HEIGHT: u8 = crate::HEIGHT as u8;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Color {
+    Black,
+    White,
+}
+
+impl Color {
+    fn is_white(self) -> bool {
+        self == Color::White
+    }
+}
+
+// Anything that can be printed, as text at the cursor
+pub trait Printable {
+    fn print(self);
+}
+
+impl Printable for &str {
+    fn print(self) {
+        state().print_bytes(self.as_bytes());
+    }
+}
+
+// Strings made with f!
+impl Printable for &[u8] {
+    fn print(self) {
+        state().print_bytes(self);
+    }
+}
+
+macro_rules! printable_number {
+    ($($t:ty),*) => {
+        $(
+            impl Printable for $t {
+                fn print(self) {
+                    state().print_bytes(self.to_string().as_bytes());
+                }
+            }
+        )*
+    };
+}
+
+printable_number!(i16, u16, i32, u32);
+
+pub struct Arduboy2 {}
+
+impl Arduboy2 {
+    pub const fn new() -> Self {
+        Arduboy2 {}
+    }
+
+    pub fn begin(&self) {}
+
+    pub fn clear(&self) {
+        state().buffer = [0; crate::BUFFER_SIZE];
+    }
+
+    pub fn display(&self) {
+        let mut state = state();
+        state.screen = state.buffer;
+        state.frames += 1;
+    }
+
+    // Every call is a new frame, as the host decides when to run them
+    pub fn next_frame(&self) -> bool {
+        true
+    }
+
+    pub fn set_frame_rate(&self, rate: u8) {
+        state().frame_rate = rate;
+    }
+
+    pub fn poll_buttons(&self) {
+        let mut state = state();
+        state.previous_buttons = state.buttons;
+        state.buttons = state.held;
+    }
+
+    pub fn pressed(&self, buttons: ButtonSet) -> bool {
+        buttons.pressed()
+    }
+
+    pub fn just_pressed(&self, buttons: ButtonSet) -> bool {
+        buttons.just_pressed()
+    }
+
+    pub fn print(&self, x: impl Printable) {
+        x.print();
+    }
+
+    pub fn set_cursor(&self, x: i16, y: i16) {
+        let mut state = state();
+        state.cursor_x = x;
+        state.cursor_y = y;
+    }
+
+    pub fn draw_pixel(&self, x: i16, y: i16, color: Color) {
+        state().draw_pixel(x, y, color.is_white());
+    }
+
+    pub fn draw_fast_hline(&self, x: i16, y: i16, w: u8, color: Color) {
+        let mut state = state();
+        for i in 0..w as i16 {
+            state.draw_pixel(x + i, y, color.is_white());
+        }
+    }
+
+    pub fn draw_fast_vline(&self, x: i16, y: i16, h: u8, color: Color) {
+        let mut state = state();
+        for i in 0..h as i16 {
+            state.draw_pixel(x, y + i, color.is_white());
+        }
+    }
+
+    pub fn draw_rect(&self, x: i16, y: i16, w: u8, h: u8, color: Color) {
+        if w == 0 || h == 0 {
+            return;
+        }
+        self.draw_fast_hline(x, y, w, color);
+        self.draw_fast_hline(x, y + h as i16 - 1, w, color);
+        self.draw_fast_vline(x, y, h, color);
+        self.draw_fast_vline(x + w as i16 - 1, y, h, color);
+    }
+
+    pub fn fill_rect(&self, x: i16, y: i16, w: u8, h: u8, color: Color) {
+        for i in 0..w as i16 {
+            self.draw_fast_vline(x + i, y, h, color);
+        }
+    }
+
+    pub fn set_rgb_led(&self, red: u8, green: u8, blue: u8) {
+        state().led = (red, green, blue);
+    }
+}
+
+impl Default for Arduboy2 {
+    fn default() -> Self {
+        Arduboy2::new()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ButtonSet {
+    pub flag_set: u8,
+}
+
+impl ButtonSet {
+    pub fn pressed(&self) -> bool {
+        state().buttons & self.flag_set == self.flag_set
+    }
+
+    pub fn just_pressed(&self) -> bool {
+        let state = state();
+        state.buttons & self.flag_set != 0 && state.previous_buttons & self.flag_set == 0
+    }
+
+    pub fn just_released(&self) -> bool {
+        let state = state();
+        state.buttons & self.flag_set == 0 && state.previous_buttons & self.flag_set != 0
+    }
+
+    pub fn not_pressed(&self) -> bool {
+        state().buttons & self.flag_set == 0
+    }
+}
+
+pub const UP: ButtonSet = ButtonSet { flag_set: 0x80 };
+pub const RIGHT: ButtonSet = ButtonSet { flag_set: 0x40 };
+pub const LEFT: ButtonSet = ButtonSet { flag_set: 0x20 };
+pub const DOWN: ButtonSet = ButtonSet { flag_set: 0x10 };
+pub const A: ButtonSet = ButtonSet { flag_set: 0x08 };
+pub const B: ButtonSet = ButtonSet { flag_set: 0x04 };
+
+// A single byte of EEPROM, after the space the Arduboy keeps for itself
+pub struct EEPROMBYTECHECKLESS {
+    address: usize,
+}
+
+impl EEPROMBYTECHECKLESS {
+    pub const fn new(idx: i16) -> Self {
+        EEPROMBYTECHECKLESS {
+            address: (idx as usize + EEPROM_STORAGE_SPACE_START) % EEPROM_SIZE,
+        }
+    }
+
+    pub fn init(&self) {}
+
+    pub fn read(&self) -> u8 {
+        state().eeprom[self.address]
+    }
+
+    pub fn update(&self, value: u8) {
+        let mut state = state();
+        if state.eeprom[self.address] != value {
+            state.eeprom[self.address] = value;
+        }
+    }
+
+    pub fn write(&self, value: u8) {
+        state().eeprom[self.address] = value;
+    }
+}
+
+// Sprites start with their width and height, then each frame is rows of 8 pixel tall columns
+// Safe to call like the library's, as the addresses only come from get_sprite_addr!
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub mod sprites {
+    use crate::state;
+
+    unsafe fn size(bitmap: *const u8) -> (i16, i16) {
+        (*bitmap as i16, *bitmap.add(1) as i16)
+    }
+
+    // First byte of a frame, with stride bytes for each column of 8 pixels
+    unsafe fn frame_start(bitmap: *const u8, frame: u8, stride: usize) -> *const u8 {
+        let (width, height) = size(bitmap);
+        let frame_size = width as usize * (height as usize).div_ceil(8) * stride;
+        bitmap.add(2 + frame as usize * frame_size)
+    }
+
+    unsafe fn bit(image: *const u8, width: i16, x: i16, y: i16, stride: usize) -> bool {
+        let byte = *image.add(((y / 8) as usize * width as usize + x as usize) * stride);
+        byte & (1 << (y % 8)) != 0
+    }
+
+    // Calls draw with every pixel of a frame and whether it's set
+    unsafe fn each_pixel(
+        bitmap: *const u8,
+        frame: u8,
+        stride: usize,
+        mut draw: impl FnMut(i16, i16, bool),
+    ) {
+        let (width, height) = size(bitmap);
+        let image = frame_start(bitmap, frame, stride);
+        for y in 0..height {
+            for x in 0..width {
+                draw(x, y, bit(image, width, x, y, stride));
+            }
+        }
+    }
+
+    // Replaces everything under the sprite
+    pub fn draw_override(x: i16, y: i16, bitmap: *const u8, frame: u8) {
+        let mut state = state();
+        unsafe {
+            each_pixel(bitmap, frame, 1, |px, py, set| {
+                state.draw_pixel(x + px, y + py, set)
+            });
+        }
+    }
+
+    // Only lights pixels, leaving the rest as they are
+    pub fn draw_self_masked(x: i16, y: i16, bitmap: *const u8, frame: u8) {
+        let mut state = state();
+        unsafe {
+            each_pixel(bitmap, frame, 1, |px, py, set| {
+                if set {
+                    state.draw_pixel(x + px, y + py, true);
+                }
+            });
+        }
+    }
+
+    // Darkens the pixels that are set
+    pub fn draw_erase(x: i16, y: i16, bitmap: *const u8, frame: u8) {
+        let mut state = state();
+        unsafe {
+            each_pixel(bitmap, frame, 1, |px, py, set| {
+                if set {
+                    state.draw_pixel(x + px, y + py, false);
+                }
+            });
+        }
+    }
+
+    // Only draws where the mask is set, the mask not starting with a width and height
+    pub fn draw_external_mask(
+        x: i16,
+        y: i16,
+        bitmap: *const u8,
+        mask: *const u8,
+        frame: u8,
+        mask_frame: u8,
+    ) {
+        let mut state = state();
+        unsafe {
+            let (width, height) = size(bitmap);
+            let mask =
+                mask.add(mask_frame as usize * width as usize * (height as usize).div_ceil(8));
+            each_pixel(bitmap, frame, 1, |px, py, set| {
+                if bit(mask, width, px, py, 1) {
+                    state.draw_pixel(x + px, y + py, set);
+                }
+            });
+        }
+    }
+
+    // The same, with each byte of image followed by its byte of mask
+    pub fn draw_plus_mask(x: i16, y: i16, bitmap: *const u8, frame: u8) {
+        let mut state = state();
+        unsafe {
+            let (width, _) = size(bitmap);
+            let mask = frame_start(bitmap, frame, 2).add(1);
+            each_pixel(bitmap, frame, 2, |px, py, set| {
+                if bit(mask, width, px, py, 2) {
+                    state.draw_pixel(x + px, y + py, set);
+                }
+            });
+        }
+    }
+}
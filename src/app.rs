@@ -0,0 +1,462 @@
+// The state of the whole game, with the screen deciding which controls and drawing are used
+// Each frame the buttons are polled and handled by update, then display draws what's left and sets
+// the LED to match, without changing it
+
+use crate::*;
+
+const EEPROM_CONFIRM_TIME: u16 = 30;
+
+// What takes the buttons, drawn over the mode
+#[derive(Clone, Copy, PartialEq)]
+enum Screen {
+    // The mode on its own
+    Main,
+    // Picking a colour for the selected band, with the colour pointed to
+    ColourMenu { pointer: u8 },
+    // Picking a mode, with the mode pointed to
+    ModeMenu { pointer: u8 },
+}
+
+pub struct App {
+    input: Input,
+    screen: Screen,
+    mode: Mode,
+    // Band selected in the colour modes
+    pointer: u8,
+    resistance: Resistance,
+    capacitor: Resistance,
+    inductor: Resistance,
+    part_editor: PartEditor,
+
+    entry: Entry,
+    drift: Drift,
+    network: Network,
+    finder: Finder,
+    divider: Divider,
+    led: Led,
+    surface: Surface,
+    rkm: Rkm,
+    power: Power,
+
+    // Preferred value series, also used by up and down when stepping through it
+    preferred_series: Series,
+    series_step: bool,
+
+    // EEPROMBYTECHECKLESS is a clone of the EEPROMBYTE struct without check digits
+    eeprom: EEPROMBYTECHECKLESS,
+    eeprom_confirm_timer: u16,
+}
+
+impl App {
+    pub const fn new() -> Self {
+        App {
+            input: Input::new(),
+            screen: Screen::Main,
+            mode: Mode::Colour,
+            pointer: 0,
+            resistance: Resistance::new(DEFAULT_BANDS),
+            capacitor: Resistance::with_component(Component::Capacitor, DEFAULT_BANDS),
+            inductor: Resistance::with_component(Component::Inductor, DEFAULT_BANDS),
+            part_editor: PartEditor::new(),
+            entry: Entry::new(),
+            drift: Drift::new(),
+            network: Network::new(),
+            finder: Finder::new(),
+            divider: Divider::new(),
+            led: Led::new(),
+            surface: Surface::new(),
+            rkm: Rkm::new(),
+            power: Power::new(),
+            preferred_series: Series::E24,
+            series_step: false,
+            eeprom: EEPROMBYTECHECKLESS::new(EEPROM_ADDR - 16),
+            eeprom_confirm_timer: 0,
+        }
+    }
+
+    // Starts the resistor with the number of bands saved as the default
+    pub fn load(&mut self) {
+        self.resistance = Resistance::new(init_eeprom(&self.eeprom));
+    }
+
+    // For replaying and recording the buttons
+    pub fn input(&mut self) -> &mut Input {
+        &mut self.input
+    }
+
+    // The part the colour modes show the bands of
+    fn part(&self) -> &Resistance {
+        match self.mode {
+            Mode::Capacitor => &self.capacitor,
+            Mode::Inductor => &self.inductor,
+            _ => &self.resistance,
+        }
+    }
+
+    fn part_mut(&mut self) -> &mut Resistance {
+        match self.mode {
+            Mode::Capacitor => &mut self.capacitor,
+            Mode::Inductor => &mut self.inductor,
+            _ => &mut self.resistance,
+        }
+    }
+
+    // Band the pointer is on, kept within the part if it has lost bands since the pointer moved
+    fn selected(&self, part: &Resistance) -> u8 {
+        self.pointer.min(part.bands - 1)
+    }
+
+    pub fn update(&mut self) {
+        self.input.poll();
+        // Counted down here so display only has to read it
+        if self.eeprom_confirm_timer > 0 {
+            self.eeprom_confirm_timer -= 1;
+        }
+
+        match self.screen {
+            Screen::ModeMenu { pointer } => self.mode_menu_update(pointer),
+            // Open mode menu button combo
+            _ if self.input.pressed(UP)
+                && self.input.pressed(DOWN)
+                && self.input.just_pressed(B) =>
+            {
+                let pointer = self.part_editor.owner.unwrap_or(self.mode) as u8;
+                self.screen = Screen::ModeMenu { pointer };
+            }
+            Screen::Main => self.main_update(),
+            Screen::ColourMenu { pointer } => self.colour_menu_update(pointer),
+        }
+    }
+
+    fn main_update(&mut self) {
+        let input = &self.input;
+        let res = &mut self.resistance;
+        // Whether to go back to the colour mode
+        let close = match self.mode {
+            Mode::Colour | Mode::Capacitor | Mode::Inductor => return self.colour_update(),
+            Mode::Entry => self.entry.update(input, res),
+            Mode::Series => preferred::update(input, res, &mut self.preferred_series),
+            Mode::Range => input.just_pressed(B),
+            Mode::Drift => self.drift.update(input),
+            // Also closes to edit a part in the colour mode
+            Mode::Network => self.network.update(input, res, &mut self.part_editor),
+            Mode::Finder => self.finder.update(input),
+            // Also closes to edit a resistor in the colour mode
+            Mode::Divider => self.divider.update(input, res, &mut self.part_editor),
+            Mode::Led => self.led.update(input, res),
+            Mode::Surface => self.surface.update(input, res),
+            Mode::Reverse => reverse::update(input, res),
+            Mode::Rkm => self.rkm.update(input, res),
+            Mode::Power => self.power.update(input),
+        };
+
+        if close {
+            self.mode = Mode::Colour;
+            // The mode may have handed back a resistor with fewer bands, which is drawn this frame
+            self.pointer = self.pointer.min(self.resistance.bands - 1);
+        }
+    }
+
+    fn mode_menu_update(&mut self, mut pointer: u8) {
+        // Switch to chosen mode
+        if self.input.just_pressed(A) {
+            // Leaving the colour mode hands an edited part back to its mode
+            if let Some(owner) = self.part_editor.owner {
+                let part = self.part_editor.finish(&mut self.resistance);
+                match owner {
+                    Mode::Network => self.network.store(part),
+                    Mode::Divider => self.divider.store(part),
+                    _ => {}
+                }
+            }
+            self.mode = MODES[pointer as usize];
+            self.screen = Screen::Main;
+            // The new mode's part may have fewer bands than the one selected
+            self.pointer = self.pointer.min(self.part().bands - 1);
+        }
+        // Cancel menu
+        if self.input.just_pressed(B) {
+            self.screen = Screen::Main;
+        }
+
+        if self.input.just_pressed(UP) && pointer > 0 {
+            pointer -= 1;
+        }
+        if self.input.just_pressed(DOWN) && pointer < MODES.len() as u8 - 1 {
+            pointer += 1;
+        }
+        if let Screen::ModeMenu { pointer: shown } = &mut self.screen {
+            *shown = pointer;
+        }
+    }
+
+    fn colour_update(&mut self) {
+        // Matched here rather than with part_mut, so the other fields can change alongside it
+        let part = match self.mode {
+            Mode::Capacitor => &mut self.capacitor,
+            Mode::Inductor => &mut self.inductor,
+            _ => &mut self.resistance,
+        };
+        let pointer = &mut self.pointer;
+
+        // Resistors loaded from other modes may have fewer bands
+        if *pointer > part.bands - 1 {
+            *pointer = part.bands - 1;
+        }
+
+        if self.input.just_pressed(A) {
+            if self.input.pressed(LEFT) && self.input.pressed(RIGHT) {
+                // Toggle stepping through preferred values button combo
+                self.series_step = !self.series_step;
+            } else {
+                let pointer = part.index_mut(*pointer).get_pointer() as u8;
+                self.screen = Screen::ColourMenu { pointer };
+            }
+        }
+        if self.input.just_pressed(B) {
+            if self.input.pressed(LEFT) && self.input.pressed(RIGHT) {
+                // Save default bands button combo, only the resistor is loaded on startup
+                if part.component == Component::Resistor {
+                    save_eeprom(&self.eeprom, part.bands);
+                    self.eeprom_confirm_timer = EEPROM_CONFIRM_TIME;
+                }
+            } else {
+                // Stick the pointer to currently selected band
+                // Other parts don't gain a third digit, so their bands stay put
+                if part.component == Component::Resistor && *pointer > 1 {
+                    if part.bands == 4 || part.mil {
+                        *pointer += 1;
+                    } else if part.bands == MIL_BANDS {
+                        // The MIL layout drops the third digit
+                        *pointer -= 1;
+                    }
+                }
+
+                // Increment, looping back to 3 after the most bands the part can have
+                // Resistors go through the MIL layout and the jumper on the way round
+                let resistor = part.component == Component::Resistor;
+                *part = if resistor && part.bands == MIL_BANDS && !part.mil {
+                    Resistance::mil()
+                } else if part.bands == JUMPER_BANDS {
+                    Resistance::with_component(part.component, MIN_BANDS)
+                } else if part.bands < part.component.max_bands() {
+                    Resistance::with_component(part.component, part.bands + 1)
+                } else if resistor {
+                    Resistance::with_component(part.component, JUMPER_BANDS)
+                } else {
+                    Resistance::with_component(part.component, MIN_BANDS)
+                };
+
+                // Prevent invalid index call
+                if *pointer > part.bands - 1 {
+                    *pointer = part.bands - 1;
+                }
+            }
+        }
+
+        if self.input.just_pressed(LEFT) {
+            if *pointer > 0 {
+                *pointer -= 1;
+            }
+        }
+        if self.input.just_pressed(RIGHT) {
+            if *pointer < part.bands - 1 {
+                *pointer += 1;
+            }
+        }
+        // Digit and multiplier bands step the whole value when using a series
+        let step = self.series_step && part.index(*pointer).is_value();
+        if self.input.just_pressed(UP) {
+            if step {
+                part.step_series(self.preferred_series, true);
+            } else {
+                part.index_mut(*pointer).change_by(1);
+            }
+        }
+        if self.input.just_pressed(DOWN) {
+            if step {
+                part.step_series(self.preferred_series, false);
+            } else {
+                part.index_mut(*pointer).change_by(-1);
+            }
+        }
+    }
+
+    fn colour_menu_update(&mut self, mut menu_pointer: u8) {
+        let pointer = self.pointer;
+        let current_colours = Band::colours_from_valtype(&self.part().index(pointer).vtype);
+
+        // Select colour choice
+        if self.input.just_pressed(A) {
+            self.part_mut()
+                .index_mut(pointer)
+                .change_to(menu_pointer as i8);
+            self.screen = Screen::Main;
+        }
+        // Cancel menu
+        if self.input.just_pressed(B) {
+            self.screen = Screen::Main;
+        }
+
+        // Move menu cursor left if possible
+        if self.input.just_pressed(LEFT) && menu_pointer % 3 != 0 {
+            if menu_pointer > 0 {
+                menu_pointer -= 1;
+            }
+        }
+        // Move menu cursor right if possible
+        if self.input.just_pressed(RIGHT) {
+            if menu_pointer < (current_colours.len() - 1) as u8 && menu_pointer % 3 != 2 {
+                menu_pointer += 1;
+            }
+        }
+        if self.input.just_pressed(UP) {
+            if menu_pointer > 2 {
+                // If pointing to central bottom place, cursor will go directly up
+                if menu_pointer == (current_colours.len() - 1) as u8
+                    && current_colours.len() % 3 != 0
+                {
+                    menu_pointer -= 2;
+                } else {
+                    menu_pointer -= 3;
+                }
+            }
+        }
+        if self.input.just_pressed(DOWN) {
+            if menu_pointer as usize + 3 < current_colours.len() {
+                menu_pointer += 3;
+            } else if current_colours.len() % 3 != 0 {
+                menu_pointer = (current_colours.len() - 1) as u8
+            }
+        }
+
+        if let Screen::ColourMenu { pointer } = &mut self.screen {
+            *pointer = menu_pointer;
+        }
+    }
+
+    pub fn display(&self) {
+        let res = &self.resistance;
+        match self.mode {
+            Mode::Colour => self.colour_display(res),
+            Mode::Entry => self.entry.display(res),
+            Mode::Series => preferred::display(res, self.preferred_series),
+            Mode::Range => range::display(res),
+            Mode::Drift => self.drift.display(res),
+            Mode::Network => self.network.display(),
+            Mode::Finder => self.finder.display(),
+            Mode::Divider => self.divider.display(),
+            Mode::Led => self.led.display(),
+            Mode::Surface => self.surface.display(res),
+            Mode::Capacitor => {
+                self.colour_display(&self.capacitor);
+                // Also show the code printed on ceramic capacitors
+                let decimal = self.capacitor.decimal();
+                if let Ok(code) = smd::Code::encode(decimal, smd::Marking::ThreeDigit) {
+                    arduboy.set_cursor(WIDTH as i16 - 2 - CHAR_WIDTH * 4, VALUE_Y);
+                    arduboy.print(code.as_str());
+                }
+            }
+            Mode::Inductor => self.colour_display(&self.inductor),
+            Mode::Reverse => reverse::display(res),
+            Mode::Rkm => self.rkm.display(res),
+            Mode::Power => self.power.display(res),
+        }
+
+        match self.screen {
+            Screen::Main => {}
+            Screen::ColourMenu { pointer } => {
+                let part = self.part();
+                draw_menu(&part.index(self.selected(part)).vtype, pointer)
+            }
+            Screen::ModeMenu { pointer } => draw_modes(pointer),
+        }
+
+        // Draw border
+        arduboy.draw_rect(0, 0, WIDTH, HEIGHT, Color::White);
+
+        self.led_display();
+    }
+
+    // Lights the LED with the colour being looked at, the mode menu leaves it as it was
+    fn led_display(&self) {
+        let part = self.part();
+        let band = part.index(self.selected(part));
+        match (self.screen, self.mode) {
+            (Screen::Main, Mode::Colour | Mode::Capacitor | Mode::Inductor) => {
+                write_led(rgb(band.colour()))
+            }
+            // Preview the LED being designed for
+            (Screen::Main, Mode::Led) => self.led.show_colour(),
+            (Screen::Main, _) => write_led(&BLACK),
+            (Screen::ColourMenu { pointer }, _) => {
+                let colours = Band::colours_from_valtype(&band.vtype);
+                write_led(rgb(colours[pointer as usize]))
+            }
+            (Screen::ModeMenu { .. }, _) => {}
+        }
+
+        // LED flashes to confirm EEPROM write
+        if self.eeprom_confirm_timer > 0 {
+            arduboy.set_rgb_led(96, 255, 16)
+        }
+    }
+
+    fn colour_display(&self, part: &Resistance) {
+        let selected = placements(part)[part.slot(self.selected(part))];
+
+        // Increase width of selected band
+        arduboy.draw_fast_vline(selected.bandx - 1, RES_Y, RES_HEIGHT, Color::White);
+        arduboy.draw_fast_vline(selected.bandx + BAND_WIDTH, RES_Y, RES_HEIGHT, Color::White);
+        // Display all bands
+        part.display();
+        part.display_value();
+
+        // Mark values that aren't made at the tolerance shown
+        if part.unexpected_series().is_some() {
+            let width = quantity_width(&part.decimal(), part.component.unit());
+            arduboy.set_cursor((WIDTH as i16 + width) / 2, VALUE_Y);
+            arduboy.print(f!(b"!\0"));
+        }
+
+        // Show which series up and down are stepping through
+        if self.series_step {
+            arduboy.set_cursor(2, VALUE_Y);
+            arduboy.print(self.preferred_series.name());
+        }
+
+        // Show which part of another mode is being edited
+        arduboy.set_cursor(WIDTH as i16 - 2 - CHAR_WIDTH * 2, VALUE_Y);
+        match self.part_editor.owner {
+            Some(Mode::Network) => {
+                arduboy.print(f!(b"P\0"));
+                arduboy.print(self.network.part_number());
+            }
+            Some(Mode::Divider) => {
+                arduboy.print(f!(b"R\0"));
+                arduboy.print(self.divider.part_number());
+            }
+            // Warn when the resistor is as likely to be being read the wrong way round
+            _ => {
+                let forward = part.plausibility();
+                if part.component == Component::Resistor
+                    && matches!(part.reversed(), Some(reversed) if reversed.plausibility() >= forward)
+                {
+                    arduboy.set_cursor(WIDTH as i16 - 2 - CHAR_WIDTH * 4, VALUE_Y);
+                    arduboy.print(f!(b"Rev?\0"));
+                }
+            }
+        }
+
+        // Draw resistor over bands
+        draw_body();
+
+        // Underline selected band text
+        arduboy.draw_fast_hline(
+            selected.x - 1,
+            TEXT_Y + CHAR_HEIGHT,
+            selected.width * CHAR_WIDTH as u8 + 1,
+            Color::White,
+        );
+    }
+}
@@ -0,0 +1,270 @@
+// Voltage divider mode, reading off the output of two resistors or designing a pair for an output voltage
+
+use crate::combination::*;
+use crate::entry::*;
+use crate::*;
+
+const UPPER: usize = 0;
+const LOWER: usize = 1;
+
+const INPUT_Y: i16 = 16;
+const OUTPUT_Y: i16 = 28;
+const SERIES_Y: i16 = 38;
+const FIELD_X: i16 = CHAR_WIDTH * 8;
+
+const PART_X: i16 = CHAR_WIDTH * 6;
+const VOLTS_X: i16 = CHAR_WIDTH * 5;
+
+// Voltages are rounded to keep them on screen
+const VOLTS_FIGURES: u8 = 4;
+const SPREAD_FIGURES: u8 = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Input,
+    Output,
+    Series,
+}
+
+pub struct Divider {
+    input: Entry,
+    output: Entry,
+    series: Series,
+    field: Field,
+    // R1 on top and R2 to ground, with the output between them
+    parts: [ColourCode; 2],
+    selected: usize,
+    designing: bool,
+    failed: bool,
+}
+
+impl Divider {
+    pub const fn new() -> Self {
+        // Two 10kΩ 5% resistors, halving the input
+        let part = ColourCode {
+            bands: DEFAULT_BANDS,
+            values: [1, 0, 0, 3, 8, 0],
+            component: Component::Resistor,
+            mil: false,
+        };
+        Divider {
+            input: Entry::with_value(Unit::Volts, [BLANK, BLANK, BLANK, BLANK, 5], UNIT_PREFIX),
+            output: Entry::with_value(Unit::Volts, [BLANK, BLANK, 3, DOT, 3], UNIT_PREFIX),
+            series: Series::E24,
+            field: Field::Output,
+            parts: [part; 2],
+            selected: UPPER,
+            designing: false,
+            failed: false,
+        }
+    }
+
+    // Returns true when the screen should be closed, including to edit a resistor
+    pub fn update(&mut self, input: &Input, res: &mut Resistance, editor: &mut PartEditor) -> bool {
+        if self.designing {
+            self.update_design(input);
+            return false;
+        }
+
+        if input.just_pressed(B) {
+            return true;
+        }
+        if input.just_pressed(A) {
+            editor.start(Mode::Divider, res, &self.parts[self.selected]);
+            return true;
+        }
+
+        if input.just_pressed(LEFT) || input.just_pressed(RIGHT) {
+            self.selected = if self.selected == UPPER { LOWER } else { UPPER };
+        }
+        if input.just_pressed(UP) || input.just_pressed(DOWN) {
+            self.designing = true;
+        }
+
+        false
+    }
+
+    fn update_design(&mut self, input: &Input) {
+        if input.just_pressed(B) {
+            self.designing = false;
+            return;
+        }
+        if input.just_pressed(A) {
+            self.design();
+            return;
+        }
+
+        if input.just_pressed(LEFT)
+            || input.just_pressed(RIGHT)
+            || input.just_pressed(UP)
+            || input.just_pressed(DOWN)
+        {
+            self.failed = false;
+        }
+
+        match self.field {
+            Field::Input => {
+                // Moving right past the prefix goes onto the next field
+                if input.just_pressed(RIGHT) && self.input.at_end() {
+                    self.field = Field::Output;
+                } else {
+                    self.input.edit(input);
+                }
+            }
+            Field::Output => {
+                if input.just_pressed(LEFT) && self.output.at_start() {
+                    self.field = Field::Input;
+                } else if input.just_pressed(RIGHT) && self.output.at_end() {
+                    self.field = Field::Series;
+                } else {
+                    self.output.edit(input);
+                }
+            }
+            Field::Series => {
+                if input.just_pressed(LEFT) {
+                    self.field = Field::Output;
+                }
+
+                let index = self.series as usize;
+                if input.just_pressed(UP) && index < SERIES.len() - 1 {
+                    self.series = SERIES[index + 1];
+                }
+                if input.just_pressed(DOWN) && index > 0 {
+                    self.series = SERIES[index - 1];
+                }
+            }
+        }
+    }
+
+    // Replaces both resistors with the closest preferred pair, going back to them if one was found
+    fn design(&mut self) {
+        let found = match (self.input.parse(), self.output.parse()) {
+            (Ok(input), Ok(output)) => find_divider(input.milli(), output.milli(), self.series),
+            _ => None,
+        };
+        let (upper, lower) = match found {
+            Some(pair) => pair,
+            None => {
+                self.failed = true;
+                return;
+            }
+        };
+
+        match (
+            Resistance::from_preferred(upper, self.series),
+            Resistance::from_preferred(lower, self.series),
+        ) {
+            (Ok(upper), Ok(lower)) => {
+                self.parts = [upper.code(), lower.code()];
+                self.designing = false;
+            }
+            _ => self.failed = true,
+        }
+    }
+
+    // Takes back the resistor once it has been edited
+    pub fn store(&mut self, part: ColourCode) {
+        self.parts[self.selected] = part;
+    }
+
+    pub fn part_number(&self) -> i16 {
+        self.selected as i16 + 1
+    }
+
+    pub fn display(&self) {
+        if self.designing {
+            self.display_design();
+            return;
+        }
+
+        let res = Resistance::from_code(&self.parts[self.selected]);
+        let value = res.decimal();
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        arduboy.print(f!(b"<R\0"));
+        arduboy.print(self.part_number());
+        arduboy.print(f!(b">\0"));
+        draw_value(&value, PART_X, TEXT_Y);
+        let x = PART_X + value_width(&value) + CHAR_WIDTH;
+        sprites::draw_override(x, TEXT_Y, get_sprite_addr!(Plus_Minus), 0);
+        arduboy.set_cursor(x + CHAR_WIDTH, TEXT_Y);
+        arduboy.print(res.tolerance_str());
+        arduboy.print(f!(b"%\0"));
+
+        res.display_bands();
+        draw_body();
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        let input = match self.input.parse() {
+            Ok(input) => input.milli(),
+            Err(_) => {
+                arduboy.print(f!(b"No input voltage\0"));
+                return;
+            }
+        };
+
+        // Worst case is one resistor at its highest while the other is at its lowest
+        let upper = Resistance::from_code(&self.parts[UPPER]);
+        let lower = Resistance::from_code(&self.parts[LOWER]);
+        let (upper_min, upper_max) = upper.bounds();
+        let (lower_min, lower_max) = lower.bounds();
+        let nominal = divider_output(input, upper.ohms(), lower.ohms());
+        let min = divider_output(input, upper_max, lower_min);
+        let max = divider_output(input, upper_min, lower_max);
+        let spread = (max - nominal).max(nominal - min);
+
+        arduboy.print(f!(b"Out\0"));
+        let len = print_volts(nominal, VOLTS_FIGURES, VOLTS_X, VALUE_Y);
+        let x = VOLTS_X + (len + 1) * CHAR_WIDTH;
+        sprites::draw_override(x, VALUE_Y, get_sprite_addr!(Plus_Minus), 0);
+        print_volts(spread, SPREAD_FIGURES, x + CHAR_WIDTH, VALUE_Y);
+    }
+
+    fn display_design(&self) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        arduboy.print(f!(b"Divider design\0"));
+
+        arduboy.set_cursor(CHAR_WIDTH, INPUT_Y);
+        arduboy.print(f!(b"Vin\0"));
+        self.input
+            .draw(FIELD_X, INPUT_Y, self.field == Field::Input);
+
+        arduboy.set_cursor(CHAR_WIDTH, OUTPUT_Y);
+        arduboy.print(f!(b"Vout\0"));
+        self.output
+            .draw(FIELD_X, OUTPUT_Y, self.field == Field::Output);
+
+        arduboy.set_cursor(CHAR_WIDTH, SERIES_Y);
+        arduboy.print(f!(b"Series\0"));
+        arduboy.set_cursor(FIELD_X, SERIES_Y);
+        arduboy.print(self.series.name());
+
+        // The voltages underline themselves
+        if self.field == Field::Series {
+            arduboy.draw_fast_hline(
+                FIELD_X - 1,
+                SERIES_Y + CHAR_HEIGHT,
+                (CHAR_WIDTH * 4) as u8 + 1,
+                Color::White,
+            );
+        }
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        if self.failed {
+            arduboy.print(f!(b"No pair found\0"));
+        } else {
+            arduboy.print(f!(b"A:Design  B:Back\0"));
+        }
+    }
+}
+
+// Prints millivolts as volts with an SI prefix, returning the number of characters
+fn print_volts(millivolts: u64, figures: u8, x: i16, y: i16) -> i16 {
+    let len = draw_number(
+        &Decimal::from_milli(millivolts).round(figures),
+        Unit::Volts,
+        x,
+        y,
+    );
+    arduboy.print(f!(b"V\0"));
+    len + 1
+}
@@ -0,0 +1,125 @@
+// Temperature drift mode, using the TCR band to find the worst case resistance over a temperature range
+
+use crate::*;
+
+// Typical operating limits for resistors, in °C
+const MIN_TEMP: i16 = -55;
+const MAX_TEMP: i16 = 155;
+const TEMP_STEP: i16 = 5;
+
+const TEMPS_Y: i16 = 16;
+const LOW_Y: i16 = 28;
+const HIGH_Y: i16 = 38;
+
+const TEMPS_X: i16 = CHAR_WIDTH * 6;
+const MIN_X: i16 = CHAR_WIDTH * 6;
+const MAX_X: i16 = CHAR_WIDTH * 14;
+
+// Bounds are rounded to keep them on screen
+const BOUND_FIGURES: u8 = 4;
+
+pub struct Drift {
+    low: i16,
+    high: i16,
+    editing_high: bool,
+}
+
+impl Drift {
+    pub const fn new() -> Self {
+        Drift {
+            low: -40,
+            high: 85,
+            editing_high: false,
+        }
+    }
+
+    // Returns true when the screen should be closed
+    pub fn update(&mut self, input: &Input) -> bool {
+        if input.just_pressed(B) {
+            return true;
+        }
+
+        if input.just_pressed(LEFT) {
+            self.editing_high = false;
+        }
+        if input.just_pressed(RIGHT) {
+            self.editing_high = true;
+        }
+
+        let temp = if self.editing_high {
+            &mut self.high
+        } else {
+            &mut self.low
+        };
+        if input.just_pressed(UP) && *temp < MAX_TEMP {
+            *temp += TEMP_STEP;
+        }
+        if input.just_pressed(DOWN) && *temp > MIN_TEMP {
+            *temp -= TEMP_STEP;
+        }
+
+        false
+    }
+
+    pub fn display(&self, res: &Resistance) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        let tcr = match res.tcr() {
+            Some(tcr) => tcr,
+            None => {
+                arduboy.print(f!(b"No TCR band\0"));
+                arduboy.set_cursor(CHAR_WIDTH, TEMPS_Y);
+                arduboy.print(f!(b"Use 6 bands\0"));
+                arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+                arduboy.print(f!(b"B:Back\0"));
+                return;
+            }
+        };
+        arduboy.print(f!(b"TCR: \0"));
+        sprites::draw_override(CHAR_WIDTH * 6, TEXT_Y, get_sprite_addr!(Plus_Minus), 0);
+        arduboy.set_cursor(CHAR_WIDTH * 7, TEXT_Y);
+        arduboy.print(tcr);
+        arduboy.print(f!(b"ppm/K\0"));
+
+        arduboy.set_cursor(CHAR_WIDTH, TEMPS_Y);
+        arduboy.print(f!(b"Temp\0"));
+        arduboy.set_cursor(TEMPS_X, TEMPS_Y);
+        print_temp(self.low);
+        arduboy.print(f!(b"C to\0"));
+        arduboy.set_cursor(TEMPS_X + CHAR_WIDTH * 9, TEMPS_Y);
+        print_temp(self.high);
+        arduboy.print(f!(b"C\0"));
+
+        // Underline the temperature being edited
+        arduboy.draw_fast_hline(
+            TEMPS_X + if self.editing_high { CHAR_WIDTH * 9 } else { 0 } - 1,
+            TEMPS_Y + CHAR_HEIGHT,
+            (CHAR_WIDTH * 5) as u8 + 1,
+            Color::White,
+        );
+
+        for (temp, y) in [(self.low, LOW_Y), (self.high, HIGH_Y)] {
+            let (min, max) = res.drift_bounds(tcr, temp);
+            arduboy.set_cursor(CHAR_WIDTH, y);
+            print_temp(temp);
+            draw_value(&Decimal::from_milliohms(min).round(BOUND_FIGURES), MIN_X, y);
+            draw_value(&Decimal::from_milliohms(max).round(BOUND_FIGURES), MAX_X, y);
+        }
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        arduboy.print(f!(b"U/D:Temp  B:Back\0"));
+    }
+}
+
+// Prints a temperature right aligned in 4 characters
+fn print_temp(temp: i16) {
+    let mut len = if temp < 0 { 2 } else { 1 };
+    let mut remaining = temp.abs();
+    while remaining >= 10 {
+        remaining /= 10;
+        len += 1;
+    }
+    for _ in len..4 {
+        arduboy.print(f!(b" \0"));
+    }
+    arduboy.print(temp);
+}
@@ -0,0 +1,201 @@
+// Value to colour mode, where the resistance is typed in and the bands are worked out
+
+use crate::*;
+
+const ENTRY_LEN: usize = 5;
+
+// Characters in the drawn field, including the prefix and unit
+pub const ENTRY_WIDTH: i16 = (ENTRY_LEN as i16 + 2) * CHAR_WIDTH;
+
+// Character codes for each place, cycled through with up and down
+pub const DOT: u8 = 10;
+pub const BLANK: u8 = 11;
+const CHAR_CODES: u8 = 12;
+
+const ENTRY_X: i16 = (WIDTH as i16 - ENTRY_WIDTH) / 2;
+const ENTRY_Y: i16 = 20;
+const STATUS_Y: i16 = 36;
+
+pub struct Entry {
+    chars: [u8; ENTRY_LEN],
+    prefix: u8,
+    cursor: u8,
+    unit: Unit,
+}
+
+impl Entry {
+    pub const fn new() -> Self {
+        Entry::with_value(Unit::Ohms, [BLANK, BLANK, 1, 0, 0], UNIT_PREFIX)
+    }
+
+    pub const fn with_value(unit: Unit, chars: [u8; ENTRY_LEN], prefix: usize) -> Self {
+        Entry {
+            chars,
+            prefix: prefix as u8,
+            cursor: ENTRY_LEN as u8 - 1,
+            unit,
+        }
+    }
+
+    // Reads the typed characters as a number, blanks are ignored
+    pub fn parse(&self) -> Result<Decimal, ValueError> {
+        let mut sig: u32 = 0;
+        let mut pow: i8 = 3 * (self.prefix as i8 - UNIT_PREFIX as i8);
+        let mut seen_digit = false;
+        let mut seen_dot = false;
+
+        for &c in self.chars.iter() {
+            match c {
+                BLANK => {}
+                DOT => {
+                    if seen_dot {
+                        return Err(ValueError::Malformed);
+                    }
+                    seen_dot = true;
+                }
+                digit => {
+                    sig = sig * 10 + digit as u32;
+                    seen_digit = true;
+                    if seen_dot {
+                        pow -= 1;
+                    }
+                }
+            }
+        }
+
+        if seen_digit {
+            Ok(Decimal::new(sig, pow))
+        } else {
+            Err(ValueError::Malformed)
+        }
+    }
+
+    // Moves between places, returning false if already at that end
+    pub fn move_cursor(&mut self, right: bool) -> bool {
+        if right && self.cursor < ENTRY_LEN as u8 {
+            self.cursor += 1;
+        } else if !right && self.cursor > 0 {
+            self.cursor -= 1;
+        } else {
+            return false;
+        }
+        true
+    }
+
+    pub fn at_start(&self) -> bool {
+        self.cursor == 0
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.cursor == ENTRY_LEN as u8
+    }
+
+    // Cycles the character at the cursor
+    pub fn change(&mut self, change: i8) {
+        if self.cursor == ENTRY_LEN as u8 {
+            // Last place is the SI prefix
+            let new = self.prefix as i8 + change;
+            if new >= 0 && new < PREFIXES.len() as i8 {
+                self.prefix = new as u8;
+            }
+        } else {
+            let c = &mut self.chars[self.cursor as usize];
+            *c = (*c as i8 + change).rem_euclid(CHAR_CODES as i8) as u8;
+        }
+    }
+
+    // Handles left, right, up and down
+    pub fn edit(&mut self, input: &Input) {
+        if input.just_pressed(LEFT) {
+            self.move_cursor(false);
+        }
+        if input.just_pressed(RIGHT) {
+            self.move_cursor(true);
+        }
+        if input.just_pressed(UP) {
+            self.change(1);
+        }
+        if input.just_pressed(DOWN) {
+            self.change(-1);
+        }
+    }
+
+    // Draws the typed value, underlining the place being edited if selected
+    pub fn draw(&self, x: i16, y: i16, selected: bool) {
+        arduboy.set_cursor(x, y);
+        for &c in self.chars.iter() {
+            match c {
+                BLANK => arduboy.print(f!(b" \0")),
+                DOT => arduboy.print(f!(b".\0")),
+                digit => arduboy.print(digit as i16),
+            }
+        }
+        draw_prefix(
+            self.unit.prefixes()[self.prefix as usize],
+            x + CHAR_WIDTH * ENTRY_LEN as i16,
+            y,
+        );
+        draw_unit(self.unit, x + CHAR_WIDTH * (ENTRY_LEN as i16 + 1), y);
+
+        if selected {
+            arduboy.draw_fast_hline(
+                x + self.cursor as i16 * CHAR_WIDTH - 1,
+                y + CHAR_HEIGHT,
+                CHAR_WIDTH as u8 + 1,
+                Color::White,
+            );
+        }
+    }
+
+    // Returns true when the screen should be closed
+    pub fn update(&mut self, input: &Input, res: &mut Resistance) -> bool {
+        if input.just_pressed(A) {
+            if let Ok(decimal) = self.parse() {
+                if res.set_decimal(decimal).is_ok() {
+                    return true;
+                }
+            }
+        }
+        if input.just_pressed(B) {
+            return true;
+        }
+
+        self.edit(input);
+
+        false
+    }
+
+    pub fn display(&self, res: &Resistance) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        arduboy.print(f!(b"Value to colour\0"));
+
+        self.draw(ENTRY_X, ENTRY_Y, true);
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        arduboy.print(f!(b"A:Set  B:Back\0"));
+
+        arduboy.set_cursor(CHAR_WIDTH, STATUS_Y);
+        match self.parse().and_then(|decimal| res.fit(decimal)) {
+            Ok(_) => arduboy.print(f!(b"Fits \0")),
+            Err(ValueError::TooPrecise) => arduboy.print(f!(b"Not exact at \0")),
+            Err(ValueError::OutOfRange) if res.bands == JUMPER_BANDS => {
+                arduboy.print(f!(b"A jumper is only 0\0"));
+                return;
+            }
+            Err(ValueError::OutOfRange) => {
+                arduboy.print(f!(b"Out of range\0"));
+                return;
+            }
+            Err(ValueError::Malformed) => {
+                arduboy.print(f!(b"Not a number\0"));
+                return;
+            }
+        }
+        arduboy.print(res.bands as i16);
+        if res.bands == JUMPER_BANDS {
+            arduboy.print(f!(b" band\0"));
+        } else {
+            arduboy.print(f!(b" bands\0"));
+        }
+    }
+}
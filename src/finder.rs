@@ -0,0 +1,207 @@
+// Combination finder mode, searching for preferred values that combine to a target resistance
+
+use crate::combination::*;
+use crate::*;
+
+const TARGET_Y: i16 = 16;
+const SERIES_Y: i16 = 28;
+const PARTS_Y: i16 = 38;
+const FIELD_X: i16 = CHAR_WIDTH * 8;
+
+const RESULT_BANDS_Y: i16 = 14;
+const RESULT_VALUES_Y: i16 = 47;
+const COLUMN_WIDTH: i16 = 40;
+
+// Results are rounded to keep them on screen
+const TOTAL_FIGURES: u8 = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Target,
+    Series,
+    Parts,
+}
+
+pub struct Finder {
+    target: Entry,
+    field: Field,
+    series: Series,
+    count: u8,
+    found: Option<Combination>,
+    searched: bool,
+}
+
+impl Finder {
+    pub const fn new() -> Self {
+        Finder {
+            target: Entry::new(),
+            field: Field::Target,
+            series: Series::E24,
+            count: 2,
+            found: None,
+            searched: false,
+        }
+    }
+
+    // Returns true when the screen should be closed
+    pub fn update(&mut self, input: &Input) -> bool {
+        // Any button press leaves the results
+        if self.searched {
+            if input.just_pressed(A) || input.just_pressed(B) {
+                self.searched = false;
+            }
+            return false;
+        }
+
+        if input.just_pressed(B) {
+            return true;
+        }
+        if input.just_pressed(A) {
+            self.found = match self.target.parse() {
+                Ok(target) => find(target.milliohms(), self.series, self.count),
+                Err(_) => None,
+            };
+            self.searched = true;
+            return false;
+        }
+
+        match self.field {
+            Field::Target => {
+                // Moving right past the prefix goes onto the next field
+                if input.just_pressed(RIGHT) && self.target.at_end() {
+                    self.field = Field::Series;
+                } else {
+                    self.target.edit(input);
+                }
+            }
+            Field::Series => {
+                if input.just_pressed(LEFT) {
+                    self.field = Field::Target;
+                }
+                if input.just_pressed(RIGHT) {
+                    self.field = Field::Parts;
+                }
+
+                let index = self.series as usize;
+                if input.just_pressed(UP) && index < SERIES.len() - 1 {
+                    self.series = SERIES[index + 1];
+                }
+                if input.just_pressed(DOWN) && index > 0 {
+                    self.series = SERIES[index - 1];
+                }
+            }
+            Field::Parts => {
+                if input.just_pressed(LEFT) {
+                    self.field = Field::Series;
+                }
+                if input.just_pressed(UP) || input.just_pressed(DOWN) {
+                    self.count = if self.count == 2 { 3 } else { 2 };
+                }
+            }
+        }
+
+        false
+    }
+
+    pub fn display(&self) {
+        if self.searched {
+            self.display_result();
+            return;
+        }
+
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        arduboy.print(f!(b"Find combination\0"));
+
+        arduboy.set_cursor(CHAR_WIDTH, TARGET_Y);
+        arduboy.print(f!(b"Target\0"));
+        self.target
+            .draw(FIELD_X, TARGET_Y, self.field == Field::Target);
+
+        arduboy.set_cursor(CHAR_WIDTH, SERIES_Y);
+        arduboy.print(f!(b"Series\0"));
+        arduboy.set_cursor(FIELD_X, SERIES_Y);
+        arduboy.print(self.series.name());
+
+        arduboy.set_cursor(CHAR_WIDTH, PARTS_Y);
+        arduboy.print(f!(b"Parts\0"));
+        arduboy.set_cursor(FIELD_X, PARTS_Y);
+        arduboy.print(self.count as i16);
+
+        // Underline the selected field, the target does its own
+        let y = match self.field {
+            Field::Target => 0,
+            Field::Series => SERIES_Y,
+            Field::Parts => PARTS_Y,
+        };
+        if y != 0 {
+            arduboy.draw_fast_hline(
+                FIELD_X - 1,
+                y + CHAR_HEIGHT,
+                (CHAR_WIDTH * 4) as u8 + 1,
+                Color::White,
+            );
+        }
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        arduboy.print(f!(b"A:Find  B:Back\0"));
+    }
+
+    fn display_result(&self) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        let (combination, target) = match (self.found, self.target.parse()) {
+            (Some(combination), Ok(target)) => (combination, target.milliohms()),
+            _ => {
+                arduboy.print(f!(b"Nothing found\0"));
+                return;
+            }
+        };
+
+        if combination.parallel {
+            arduboy.print(f!(b"Parallel\0"));
+        } else {
+            arduboy.print(f!(b"Series\0"));
+        }
+        draw_value(
+            &Decimal::from_milliohms(combination.total).round(TOTAL_FIGURES),
+            CHAR_WIDTH * 10,
+            TEXT_Y,
+        );
+
+        // Each part in a column, with its bands above its value
+        let figures = self.series.figures();
+        let first_x = (WIDTH as i16 - combination.count as i16 * COLUMN_WIDTH) / 2;
+        let bands_width = (figures as i16 + 1) * BANDS_GAP - 2;
+        for i in 0..combination.count as usize {
+            let x = first_x + i as i16 * COLUMN_WIDTH;
+            let part = &combination.parts[i];
+            draw_bands(
+                part,
+                figures,
+                x + (COLUMN_WIDTH - bands_width) / 2,
+                RESULT_BANDS_Y,
+            );
+            draw_number(part, Unit::Ohms, x + 2, RESULT_VALUES_Y);
+        }
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        arduboy.print(f!(b"Error: \0"));
+        print_percent(combination.error(target));
+    }
+}
+
+// Prints hundredths of a percent with a sign, e.g. +0.12%
+fn print_percent(hundredths: i32) {
+    if hundredths < 0 {
+        arduboy.print(f!(b"-\0"));
+    } else {
+        arduboy.print(f!(b"+\0"));
+    }
+    let hundredths = hundredths.unsigned_abs();
+    arduboy.print(hundredths / 100);
+    arduboy.print(f!(b".\0"));
+    if hundredths % 100 < 10 {
+        arduboy.print(f!(b"0\0"));
+    }
+    arduboy.print(hundredths % 100);
+    arduboy.print(f!(b"%\0"));
+}
@@ -0,0 +1,209 @@
+// The buttons as the game sees them, either from the Arduboy or replayed from a script
+// A script has a line for each run of frames, with the buttons held separated by spaces and
+// then how many frames they're held for if it's more than one, e.g. "UP DOWN B" or "RIGHT 3"
+// A line with only a number is that many frames of nothing held, and an empty line is one
+// Recordings are written the same way, so they can be replayed by the simulate example or tests
+
+use crate::*;
+use core::fmt::{self, Write};
+use core::str::Lines;
+
+const BUTTONS: [(ButtonSet, &str); 6] = [
+    (UP, "UP"),
+    (DOWN, "DOWN"),
+    (LEFT, "LEFT"),
+    (RIGHT, "RIGHT"),
+    (A, "A"),
+    (B, "B"),
+];
+
+// Runs of frames kept while recording, which stops once they're used up
+const RECORDING_RUNS: usize = 64;
+
+// The buttons held at the last poll and the poll before that, with any script replayed or recorded
+pub struct Input {
+    held: u8,
+    previous: u8,
+    replay: Option<Replay>,
+    recording: Recording,
+}
+
+struct Replay {
+    lines: Lines<'static>,
+    buttons: u8,
+    frames: u16,
+}
+
+impl Replay {
+    // The buttons for the next frame, or None at the end of the script
+    fn next(&mut self) -> Option<u8> {
+        while self.frames == 0 {
+            let (buttons, frames) = parse_line(self.lines.next()?).ok()?;
+            self.buttons = buttons;
+            self.frames = frames;
+        }
+        self.frames -= 1;
+        Some(self.buttons)
+    }
+
+    // Whether there are any frames still to play
+    fn has_frames(&self) -> bool {
+        self.frames > 0
+            || self
+                .lines
+                .clone()
+                .any(|line| matches!(parse_line(line), Ok((_, frames)) if frames > 0))
+    }
+}
+
+struct Recording {
+    on: bool,
+    runs: [(u8, u8); RECORDING_RUNS],
+    len: usize,
+}
+
+impl Recording {
+    const fn new() -> Self {
+        Recording {
+            on: false,
+            runs: [(0, 0); RECORDING_RUNS],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, buttons: u8) {
+        if self.len > 0 {
+            let last = &mut self.runs[self.len - 1];
+            if last.0 == buttons && last.1 < u8::MAX {
+                last.1 += 1;
+                return;
+            }
+        }
+        if self.len < RECORDING_RUNS {
+            self.runs[self.len] = (buttons, 1);
+            self.len += 1;
+        } else {
+            self.on = false;
+        }
+    }
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Input::new()
+    }
+}
+
+// The buttons on a line of a script, and the number of frames they're held for
+pub fn parse_line(line: &str) -> Result<(u8, u16), &str> {
+    let mut buttons = 0;
+    let mut frames = 1;
+    let mut words = line.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        if let Some((button, _)) = BUTTONS.iter().find(|(_, name)| *name == word) {
+            buttons |= button.flag_set;
+        } else if words.peek().is_none() {
+            // Only the last word can be the number of frames
+            frames = word.parse().map_err(|_| word)?;
+        } else {
+            return Err(word);
+        }
+    }
+    Ok((buttons, frames))
+}
+
+impl Input {
+    pub const fn new() -> Self {
+        Input {
+            held: 0,
+            previous: 0,
+            replay: None,
+            recording: Recording::new(),
+        }
+    }
+
+    // Plays the script instead of the buttons from the next poll, going back to them at its end
+    // Gives back the first word that isn't a button or a number of frames, without replaying
+    pub fn play(&mut self, script: &'static str) -> Result<(), &'static str> {
+        for line in script.lines() {
+            parse_line(line)?;
+        }
+        let script = Replay {
+            lines: script.lines(),
+            buttons: 0,
+            frames: 0,
+        };
+        self.replay = Some(script).filter(Replay::has_frames);
+        Ok(())
+    }
+
+    // Whether a script has frames still to play
+    pub fn replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    // Starts recording the buttons of every poll from the next one, forgetting any earlier recording
+    pub fn record(&mut self) {
+        self.recording = Recording::new();
+        self.recording.on = true;
+    }
+
+    pub fn stop_recording(&mut self) {
+        self.recording.on = false;
+    }
+
+    // Writes what's been recorded as a script
+    pub fn write_recording(&self, out: &mut impl Write) -> fmt::Result {
+        let recording = &self.recording;
+        for &(buttons, frames) in &recording.runs[..recording.len] {
+            let mut names = BUTTONS
+                .iter()
+                .filter(|(button, _)| buttons & button.flag_set != 0)
+                .map(|(_, name)| *name);
+            if let Some(name) = names.next() {
+                out.write_str(name)?;
+                for name in names {
+                    write!(out, " {}", name)?;
+                }
+                if frames > 1 {
+                    write!(out, " {}", frames)?;
+                }
+            } else if frames > 1 {
+                write!(out, "{}", frames)?;
+            }
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    // Reads the buttons for this frame, used in place of arduboy.poll_buttons()
+    pub fn poll(&mut self) {
+        arduboy.poll_buttons();
+        let buttons = match self.replay.as_mut().and_then(Replay::next) {
+            Some(buttons) => buttons,
+            None => BUTTONS
+                .iter()
+                .filter(|(button, _)| button.pressed())
+                .fold(0, |buttons, (button, _)| buttons | button.flag_set),
+        };
+        // Finished as soon as the last frame is played, so the caller can stop there
+        if !matches!(&self.replay, Some(script) if script.has_frames()) {
+            self.replay = None;
+        }
+        self.previous = self.held;
+        self.held = buttons;
+        if self.recording.on {
+            self.recording.push(buttons);
+        }
+    }
+
+    // Whether all the buttons are held
+    pub fn pressed(&self, buttons: ButtonSet) -> bool {
+        self.held & buttons.flag_set == buttons.flag_set
+    }
+
+    // Whether any of the buttons has been pressed since the last poll
+    pub fn just_pressed(&self, buttons: ButtonSet) -> bool {
+        self.held & buttons.flag_set != 0 && self.previous & buttons.flag_set == 0
+    }
+}
@@ -0,0 +1,303 @@
+// LED resistor mode, working out the current limiting resistor for an LED
+
+use crate::entry::*;
+use crate::*;
+
+const SUPPLY_Y: i16 = 14;
+const COLOUR_Y: i16 = 23;
+const FORWARD_Y: i16 = 32;
+const CURRENT_Y: i16 = 41;
+const FIELD_X: i16 = CHAR_WIDTH * 9;
+
+const VALUE_X: i16 = CHAR_WIDTH * 8;
+const POWER_X: i16 = CHAR_WIDTH * 10;
+
+// Typical LED colours and forward voltages, picking one fills in the forward voltage
+const LED_COLOURS: [RGB; 6] = [RED, ORANGE, YELLOW, GREEN, BLUE, WHITE];
+const LED_NAMES: [&str; 6] = [
+    "Red\0", "Orange\0", "Yellow\0", "Green\0", "Blue\0", "White\0",
+];
+const LED_FORWARD: [[u8; 5]; 6] = [
+    [BLANK, BLANK, 1, DOT, 8],
+    [BLANK, BLANK, 2, DOT, 0],
+    [BLANK, BLANK, 2, DOT, 1],
+    [BLANK, BLANK, 2, DOT, 2],
+    [BLANK, BLANK, 3, DOT, 2],
+    [BLANK, BLANK, 3, DOT, 2],
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Supply,
+    Colour,
+    Forward,
+    Current,
+}
+
+// Why there's no resistor to show
+enum LedError {
+    Malformed,
+    SupplyTooLow,
+    OutOfRange,
+}
+
+pub struct Led {
+    supply: Entry,
+    colour: u8,
+    forward: Entry,
+    current: Entry,
+    field: Field,
+    series: Series,
+    calculated: bool,
+}
+
+impl Led {
+    pub const fn new() -> Self {
+        Led {
+            supply: Entry::with_value(Unit::Volts, [BLANK, BLANK, BLANK, BLANK, 5], UNIT_PREFIX),
+            colour: 0,
+            forward: Entry::with_value(Unit::Volts, LED_FORWARD[0], UNIT_PREFIX),
+            current: Entry::with_value(Unit::Amps, [BLANK, BLANK, BLANK, 2, 0], 0),
+            field: Field::Supply,
+            series: Series::E24,
+            calculated: false,
+        }
+    }
+
+    // Returns true when the screen should be closed
+    pub fn update(&mut self, input: &Input, res: &mut Resistance) -> bool {
+        if self.calculated {
+            self.update_result(input, res)
+        } else {
+            self.update_fields(input)
+        }
+    }
+
+    fn update_fields(&mut self, input: &Input) -> bool {
+        if input.just_pressed(B) {
+            return true;
+        }
+        if input.just_pressed(A) {
+            self.calculated = true;
+            return false;
+        }
+
+        match self.field {
+            Field::Supply => {
+                // Moving right past the prefix goes onto the next field
+                if input.just_pressed(RIGHT) && self.supply.at_end() {
+                    self.field = Field::Colour;
+                } else {
+                    self.supply.edit(input);
+                }
+            }
+            Field::Colour => {
+                if input.just_pressed(LEFT) {
+                    self.field = Field::Supply;
+                }
+                if input.just_pressed(RIGHT) {
+                    self.field = Field::Forward;
+                }
+
+                let last = LED_COLOURS.len() as u8 - 1;
+                let changed = if input.just_pressed(UP) {
+                    self.colour = if self.colour == last {
+                        0
+                    } else {
+                        self.colour + 1
+                    };
+                    true
+                } else if input.just_pressed(DOWN) {
+                    self.colour = if self.colour == 0 {
+                        last
+                    } else {
+                        self.colour - 1
+                    };
+                    true
+                } else {
+                    false
+                };
+                if changed {
+                    self.forward = Entry::with_value(
+                        Unit::Volts,
+                        LED_FORWARD[self.colour as usize],
+                        UNIT_PREFIX,
+                    );
+                }
+            }
+            Field::Forward => {
+                if input.just_pressed(LEFT) && self.forward.at_start() {
+                    self.field = Field::Colour;
+                } else if input.just_pressed(RIGHT) && self.forward.at_end() {
+                    self.field = Field::Current;
+                } else {
+                    self.forward.edit(input);
+                }
+            }
+            Field::Current => {
+                if input.just_pressed(LEFT) && self.current.at_start() {
+                    self.field = Field::Forward;
+                } else {
+                    self.current.edit(input);
+                }
+            }
+        }
+
+        false
+    }
+
+    fn update_result(&mut self, input: &Input, res: &mut Resistance) -> bool {
+        if input.just_pressed(B) {
+            self.calculated = false;
+            return false;
+        }
+        if input.just_pressed(A) {
+            // Use the result as the main resistor
+            if let Ok(result) = self.resistor() {
+                *res = result;
+                return true;
+            }
+        }
+
+        let index = self.series as usize;
+        if (input.just_pressed(UP) || input.just_pressed(RIGHT)) && index < SERIES.len() - 1 {
+            self.series = SERIES[index + 1];
+        }
+        if (input.just_pressed(DOWN) || input.just_pressed(LEFT)) && index > 0 {
+            self.series = SERIES[index - 1];
+        }
+
+        false
+    }
+
+    // Voltage left across the resistor once the LED has dropped its share, in millivolts
+    fn resistor_volts(&self) -> Result<u64, LedError> {
+        match (self.supply.parse(), self.forward.parse()) {
+            (Ok(supply), Ok(forward)) => {
+                if supply.milli() > forward.milli() {
+                    Ok(supply.milli() - forward.milli())
+                } else {
+                    Err(LedError::SupplyTooLow)
+                }
+            }
+            _ => Err(LedError::Malformed),
+        }
+    }
+
+    // Nearest preferred value to the exact resistance needed
+    fn resistor(&self) -> Result<Resistance, LedError> {
+        let volts = self.resistor_volts()?;
+        let current = match self.current.parse() {
+            Ok(current) if current.micro() > 0 => current.micro(),
+            _ => return Err(LedError::Malformed),
+        };
+
+        // mΩ = mV * 1,000,000 / µA
+        let exact = (volts as u128 * 1_000_000 / current as u128).min(Milliohms::MAX as u128);
+        self.series
+            .nearest(Decimal::from_milliohms(exact as Milliohms))
+            .and_then(|nearest| Resistance::from_preferred(nearest, self.series).ok())
+            .ok_or(LedError::OutOfRange)
+    }
+
+    // Lights the LED in the colour being designed for
+    pub fn show_colour(&self) {
+        write_led(&LED_COLOURS[self.colour as usize]);
+    }
+
+    pub fn display(&self) {
+        if self.calculated {
+            self.display_result();
+            return;
+        }
+
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        arduboy.print(f!(b"LED resistor\0"));
+
+        arduboy.set_cursor(CHAR_WIDTH, SUPPLY_Y);
+        arduboy.print(f!(b"Supply\0"));
+        self.supply
+            .draw(FIELD_X, SUPPLY_Y, self.field == Field::Supply);
+
+        arduboy.set_cursor(CHAR_WIDTH, COLOUR_Y);
+        arduboy.print(f!(b"LED\0"));
+        arduboy.set_cursor(FIELD_X, COLOUR_Y);
+        arduboy.print(LED_NAMES[self.colour as usize]);
+
+        arduboy.set_cursor(CHAR_WIDTH, FORWARD_Y);
+        arduboy.print(f!(b"Forward\0"));
+        self.forward
+            .draw(FIELD_X, FORWARD_Y, self.field == Field::Forward);
+
+        arduboy.set_cursor(CHAR_WIDTH, CURRENT_Y);
+        arduboy.print(f!(b"Current\0"));
+        self.current
+            .draw(FIELD_X, CURRENT_Y, self.field == Field::Current);
+
+        // The entries underline themselves
+        if self.field == Field::Colour {
+            arduboy.draw_fast_hline(
+                FIELD_X - 1,
+                COLOUR_Y + CHAR_HEIGHT,
+                (CHAR_WIDTH * 6) as u8 + 1,
+                Color::White,
+            );
+        }
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        arduboy.print(f!(b"A:Calc  B:Back\0"));
+    }
+
+    fn display_result(&self) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        let res = match self.resistor() {
+            Ok(res) => res,
+            Err(error) => {
+                match error {
+                    LedError::Malformed => arduboy.print(f!(b"Not a number\0")),
+                    LedError::SupplyTooLow => arduboy.print(f!(b"Supply too low\0")),
+                    LedError::OutOfRange => arduboy.print(f!(b"Out of range\0")),
+                }
+                arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+                arduboy.print(f!(b"B:Back\0"));
+                return;
+            }
+        };
+
+        arduboy.print(f!(b"<\0"));
+        arduboy.print(self.series.name());
+        arduboy.print(f!(b">\0"));
+        let value = res.decimal();
+        draw_value(&value, VALUE_X, TEXT_Y);
+        let x = VALUE_X + value_width(&value) + CHAR_WIDTH;
+        sprites::draw_override(x, TEXT_Y, get_sprite_addr!(Plus_Minus), 0);
+        arduboy.set_cursor(x + CHAR_WIDTH, TEXT_Y);
+        arduboy.print(res.tolerance_str());
+        arduboy.print(f!(b"%\0"));
+
+        res.display_bands();
+        draw_body();
+
+        // What actually flows through the rounded resistor, and the heat it has to take
+        let volts = self.resistor_volts().unwrap_or(0);
+        let current = (volts as u128 * 1_000_000 / res.ohms().max(1) as u128).min(u64::MAX as u128);
+        let power = (volts as u128 * current / 1000).min(u64::MAX as u128) as u64;
+        let current = current as u64;
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        print_milli(current);
+        arduboy.print(f!(b"A\0"));
+        arduboy.set_cursor(POWER_X, VALUE_Y);
+        print_milli(power);
+        arduboy.print(f!(b"W\0"));
+    }
+}
+
+// Prints millionths as thousandths to one decimal place, e.g. 19.6m
+fn print_milli(micro: u64) {
+    let tenths = (micro + 50) / 100;
+    arduboy.print((tenths / 10) as u32);
+    arduboy.print(f!(b".\0"));
+    arduboy.print((tenths % 10) as u32);
+    arduboy.print(f!(b"m\0"));
+}
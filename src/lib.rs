@@ -1,321 +1,348 @@
 #![no_std]
 #![allow(non_upper_case_globals)]
-#![feature(const_fn_floating_point_arithmetic)]
 
 use core::i16;
+use core::ptr::addr_of_mut;
+
+mod app;
+mod divider;
+mod drift;
+mod entry;
+mod finder;
+pub mod input;
+mod led;
+mod network;
+mod power;
+mod preferred;
+mod range;
+mod reverse;
+mod rkm;
+mod surface;
+
+use app::App;
+use divider::Divider;
+use drift::Drift;
+use entry::Entry;
+use finder::Finder;
+use input::Input;
+use led::Led;
+use network::Network;
+use power::Power;
+use resistor_core::band::*;
+use resistor_core::colour::*;
+use resistor_core::eseries::*;
+use resistor_core::format::*;
+use resistor_core::letters::*;
+use resistor_core::tables::*;
+use resistor_core::value::*;
+use resistor_core::{combination, smd};
+use rkm::Rkm;
+use surface::Surface;
 
 //Include the Arduboy Library
 //Initialize the arduboy object
+#[cfg(not(feature = "host"))]
 use arduboy_rust::prelude::*;
+// Or the stand-in for it, to run on a computer
+#[cfg(feature = "host")]
+use arduboy_mock::prelude::*;
 const arduboy: Arduboy2 = Arduboy2::new();
 
 const CHAR_WIDTH: i16 = 6;
 const CHAR_HEIGHT: i16 = 8;
 
-const MAX_BANDS: u8 = 6;
-const MIN_BANDS: u8 = 3;
 const DEFAULT_BANDS: u8 = 4;
 
-// Rounds down to nearest multiple
-const fn round_down_to(unrounded: i16, multiple: i16) -> i16 {
-    if unrounded >= 0 {
-        (unrounded / multiple) * multiple
-    } else {
-        ((unrounded - multiple + 1) / multiple) * multiple
+// Characters of text each kind of band has above the resistor
+const fn text_width(vtype: &ValType) -> u8 {
+    match vtype {
+        ValType::Digit => 1,
+        ValType::Multiplier => 5,
+        ValType::Tolerance => 5,
+        ValType::TCR => 4,
+        ValType::CapMultiplier => 5,
+        ValType::CapTolerance => 5,
+        ValType::Voltage => 4,
+        ValType::InductorMultiplier => 5,
+        ValType::InductorTolerance => 5,
+        ValType::Jumper => 9,
+        ValType::FailureRate => 7,
     }
 }
 
-// What the resistor bands can represent
-enum ValType {
-    Digit,
-    Multiplier,
-    Tolerance,
-    TCR,
-}
-
-// A single band of the resistor
-#[repr(C)]
-struct Band {
-    value: c_char,
-    show: bool,
-    vtype: ValType,
+// Where a band and its text are drawn
+#[derive(Clone, Copy)]
+struct Placement {
     x: i16,
-    y: i16,
     width: u8,
     bandx: i16,
 }
 
-impl Band {
-    const fn new(show: bool, vtype: ValType, x: i16, bandx: i16) -> Self {
-        let width = Band::get_width(&vtype);
-
-        Band {
-            value: 0,
-            show,
-            vtype,
-            x,
-            y: TEXT_Y,
-            width,
-            bandx,
-        }
+// Spaces out the text of the bands that are shown, giving a placement for every band in order
+fn placements(res: &Resistance) -> [Placement; 6] {
+    let bands = res.all();
+    let mut placements = [Placement {
+        x: 0,
+        width: 0,
+        bandx: 0,
+    }; 6];
+    for (i, placement) in placements.iter_mut().enumerate() {
+        placement.width = text_width(&bands[i].vtype);
+        placement.bandx = BAND_Xs[i];
     }
 
-    const fn get_width(vtype: &ValType) -> u8 {
-        match vtype {
-            ValType::Digit => 1,
-            ValType::Multiplier => 4,
-            ValType::Tolerance => 5,
-            ValType::TCR => 4,
-        }
+    // A jumper is drawn as one black band in the middle
+    if res.bands == JUMPER_BANDS {
+        placements[0].x = (WIDTH as i16 - JUMPER_WIDTH) / 2;
+        placements[0].bandx = (WIDTH as i16 - BAND_WIDTH) / 2;
+        return placements;
     }
 
-    fn change_by(&mut self, increment: i8) {
-        let new = self.value + increment;
-
-        let (min, max) = match self.vtype {
-            ValType::Digit => {
-                self.value = new.rem_euclid(10);
-                return;
-            }
-            ValType::Multiplier => (-3, 9),
-            ValType::Tolerance => (0, 9),
-            ValType::TCR => (0, 8),
-        };
+    let three_digits = res.value100.show;
+    let tolerance = res.tolerance_index.show;
+    let last = res.tcr_index.show;
+    let last_width = match res.tcr_index.vtype {
+        ValType::FailureRate => FAILURE_RATE_WIDTH,
+        ValType::Voltage => VOLTAGE_WIDTH,
+        _ => TEXT_WIDTHS[3],
+    };
 
-        if new <= max && new >= min {
-            self.value = new
-        }
+    // Procedural Spacing
+    let tot_width = (if three_digits {
+        TEXT_WIDTHS[0]
+    } else {
+        TEXT_WIDTHS[1]
+    } + if tolerance { TEXT_WIDTHS[2] } else { 0 }
+        + if last { last_width } else { 0 });
+    // A gap either side of each group of text
+    let groups = 1 + tolerance as i16 + last as i16;
+    let spacing: f32 = (WIDTH as i16 - tot_width) as f32 / (groups + 1) as f32;
+
+    // Start with left spacing
+    let mut x: f32 = spacing + 1.0;
+
+    placements[0].x = x as i16;
+    x += CHAR_WIDTH as f32;
+    placements[1].x = x as i16;
+    if three_digits {
+        x += CHAR_WIDTH as f32;
     }
-
-    fn change_to(&mut self, new: i8) {
-        if let ValType::Multiplier = self.vtype {
-            self.value = new - 3
-        } else {
-            self.value = new
-        }
+    placements[2].x = x as i16;
+    x += CHAR_WIDTH as f32;
+    placements[3].x = x as i16;
+    if tolerance {
+        x += (CHAR_WIDTH * placements[3].width as i16) as f32 + spacing;
+    }
+    placements[4].x = x as i16;
+    if last {
+        x += (CHAR_WIDTH * placements[4].width as i16) as f32 + spacing;
     }
+    placements[5].x = x as i16;
 
-    fn get_pointer(&self) -> i8 {
-        if let ValType::Multiplier = self.vtype {
-            self.value + 3
-        } else {
-            self.value
-        }
+    placements
+}
+
+// Prints what a band stands for above the resistor, then draws the band
+fn draw_band_text(band: &Band, placement: &Placement) {
+    if !band.show {
+        return;
     }
 
-    fn display(&self) {
-        if !self.show {
-            return;
+    // Display number
+    arduboy.set_cursor(placement.x, TEXT_Y);
+    match band.vtype {
+        ValType::Digit => arduboy.print(band.value as i16),
+        ValType::Multiplier | ValType::CapMultiplier | ValType::InductorMultiplier => {
+            // What the digits are multiplied by, e.g. x10k
+            arduboy.print(f!(b"x\0"));
+            arduboy.print(Formatted::new(Decimal::new(1, band.value), &PREFIXES).as_str());
         }
-
-        // Display number
-        arduboy.set_cursor(self.x, self.y);
-        match self.vtype {
-            ValType::Digit => arduboy.print(self.value as i16),
-            ValType::Multiplier => {
-                let repeat: i16 = (self.value.rem_euclid(3)).into();
-                match repeat {
-                    1 => arduboy.print(f!(b"0 \0")),
-                    2 => arduboy.print(f!(b"00\0")),
-                    _ => arduboy.print(f!(b"  \0")),
-                }
-
-                arduboy.print(PREFIXES[(round_down_to(self.value.into(), 3) / 3 + 1) as usize]);
-                sprites::draw_override(
-                    self.x + CHAR_WIDTH * (self.width as i16 - 1),
-                    self.y,
-                    get_sprite_addr!(Ohm),
-                    0,
-                );
-            }
-            ValType::Tolerance => {
-                arduboy.print(TOLERANCES[self.value as usize]);
-                arduboy.print(f!(b"%\0"));
-            }
-            ValType::TCR => {
-                let tcr = TCRs[self.value as usize];
-                sprites::draw_override(
-                    self.x + CHAR_WIDTH * (4 - tcr.len() as i16),
-                    self.y,
-                    get_sprite_addr!(Plus_Minus),
-                    0,
-                );
-                arduboy.set_cursor(self.x + CHAR_WIDTH * (4 - tcr.len() as i16 + 1), self.y);
-                arduboy.print(tcr);
-                arduboy.print(f!(b"TCR\0"));
-            }
+        ValType::Tolerance => {
+            arduboy.print(TOLERANCES[band.value as usize]);
+            arduboy.print(f!(b"%\0"));
+        }
+        ValType::TCR => {
+            let tcr = TCRs[band.value as usize];
+            sprites::draw_override(
+                placement.x + CHAR_WIDTH * (4 - tcr.len() as i16),
+                TEXT_Y,
+                get_sprite_addr!(Plus_Minus),
+                0,
+            );
+            arduboy.set_cursor(
+                placement.x + CHAR_WIDTH * (4 - tcr.len() as i16 + 1),
+                TEXT_Y,
+            );
+            arduboy.print(tcr);
+            arduboy.print(f!(b"TCR\0"));
+        }
+        ValType::CapTolerance => {
+            arduboy.print(CAPACITOR_TOLERANCE_LETTERS[band.value as usize]);
+            arduboy.print(f!(b" \0"));
+            arduboy.print(CAPACITOR_TOLERANCES[band.value as usize]);
+            arduboy.print(f!(b"%\0"));
+        }
+        ValType::Voltage => {
+            arduboy.print(VOLTAGES[band.value as usize]);
+            arduboy.print(f!(b"V\0"));
+        }
+        ValType::InductorTolerance => {
+            arduboy.print(INDUCTOR_TOLERANCES[band.value as usize]);
+            arduboy.print(f!(b"%\0"));
+        }
+        ValType::Jumper => {
+            arduboy.print(f!(b"0\0"));
+            draw_unit(Unit::Ohms, placement.x + CHAR_WIDTH, TEXT_Y);
+            arduboy.set_cursor(placement.x + CHAR_WIDTH * 2, TEXT_Y);
+            arduboy.print(f!(b" jumper\0"));
+        }
+        ValType::FailureRate => {
+            arduboy.print(FAILURE_RATE_LETTERS[band.value as usize]);
+            arduboy.print(f!(b" \0"));
+            arduboy.print(FAILURE_RATES[band.value as usize]);
+            arduboy.print(f!(b"%\0"));
         }
-
-        // Display band
-        sprites::draw_override(self.bandx, BAND_Y, get_sprite_addr!(Band), self.get_rgb().3);
-        // Display abbreviation
-        sprites::draw_self_masked(
-            self.bandx + (BAND_WIDTH - ABBR_WIDTH as i16) / 2,
-            ABBR_Y as i16,
-            get_sprite_addr!(Abbreviations),
-            self.get_rgb().4,
-        )
     }
 
-    fn display_rgb(&self) {
-        write_led(self.get_rgb());
+    draw_band(band, placement);
+}
+
+// Draws the band itself and its colour's abbreviation
+fn draw_band(band: &Band, placement: &Placement) {
+    if !band.show {
+        return;
     }
 
-    fn get_rgb(&self) -> &RGB {
-        let arr = Band::rgb_arr_from_valtype(&self.vtype);
-        if let ValType::Multiplier = self.vtype {
-            &arr[self.value as usize + 3]
-        } else {
-            &arr[self.value as usize]
+    let rgb = rgb(band.colour());
+    // Display band
+    sprites::draw_override(placement.bandx, BAND_Y, get_sprite_addr!(Band), rgb.3);
+    // Display abbreviation
+    sprites::draw_self_masked(
+        placement.bandx + (BAND_WIDTH - ABBR_WIDTH as i16) / 2,
+        ABBR_Y as i16,
+        get_sprite_addr!(Abbreviations),
+        rgb.4,
+    )
+}
+
+// Drawing for the core crate's parts, which knows nothing of the screen
+trait Draw {
+    fn display(&self);
+    fn display_bands(&self);
+    fn display_value(&self);
+}
+
+impl Draw for Resistance {
+    fn display(&self) {
+        for (band, placement) in self.all().into_iter().zip(placements(self).iter()) {
+            draw_band_text(band, placement);
         }
     }
 
-    fn rgb_arr_from_valtype(vtype: &ValType) -> &[RGB] {
-        match vtype {
-            ValType::Digit => &VALUE_COLORS,
-            ValType::Multiplier => &MULTIPLIER_COLORS,
-            ValType::Tolerance => &TOLERANCE_COLORS,
-            ValType::TCR => &TCR_COLORS,
+    // Just the bands, for modes that put their own text around the resistor
+    fn display_bands(&self) {
+        for (band, placement) in self.all().into_iter().zip(placements(self).iter()) {
+            draw_band(band, placement);
         }
     }
-}
 
-// All bands of the resistor
-#[repr(C)]
-struct Resistance {
-    value1: Band,
-    value10: Band,
-    value100: Band,
-    multiplier_pow: Band,
-    tolerance_index: Band,
-    tcr_index: Band,
-    bands: u8,
+    // Shows the whole resistance as a single value, e.g. 4.7kΩ
+    fn display_value(&self) {
+        let decimal = self.decimal();
+        let unit = self.component.unit();
+        draw_quantity(
+            &decimal,
+            unit,
+            (WIDTH as i16 - quantity_width(&decimal, unit)) / 2,
+            VALUE_Y,
+        );
+    }
 }
 
-impl Resistance {
-    const fn new(bands: u8) -> Self {
-        // Procedural Spacing
-        let tot_width = (if bands >= 5 {
-            TEXT_WIDTHS[0]
-        } else {
-            TEXT_WIDTHS[1]
-        } + if bands >= 4 { TEXT_WIDTHS[2] } else { 0 }
-            + if bands >= 6 { TEXT_WIDTHS[3] } else { 0 });
-        let spacing: f32 =
-            (WIDTH as i16 - tot_width) as f32 / (bands - if bands >= 5 { 2 } else { 1 }) as f32;
+fn value_width(decimal: &Decimal) -> i16 {
+    quantity_width(decimal, Unit::Ohms)
+}
 
-        // Start with left spacing
-        let mut x: f32 = spacing + 1.0;
+fn quantity_width(decimal: &Decimal, unit: Unit) -> i16 {
+    (Formatted::new(*decimal, unit.prefixes()).len() as i16 + 1) * CHAR_WIDTH
+}
 
-        let value1 = Band::new(true, ValType::Digit, x as i16, BAND_Xs[0]);
-        x += CHAR_WIDTH as f32;
-        let value10 = Band::new(true, ValType::Digit, x as i16, BAND_Xs[1]);
-        if bands >= 5 {
-            x += CHAR_WIDTH as f32;
-        }
-        let value100 = Band::new(bands >= 5, ValType::Digit, x as i16, BAND_Xs[2]);
-        x += CHAR_WIDTH as f32;
-        let multiplier_pow = Band::new(true, ValType::Multiplier, x as i16, BAND_Xs[3]);
-        if bands >= 4 {
-            x += (CHAR_WIDTH * Band::get_width(&ValType::Multiplier) as i16) as f32 + spacing;
-        }
-        let tolerance_index = Band::new(bands >= 4, ValType::Tolerance, x as i16, BAND_Xs[4]);
+// Prints a resistance as a single number with an SI prefix and ohm sign
+fn draw_value(decimal: &Decimal, x: i16, y: i16) {
+    draw_quantity(decimal, Unit::Ohms, x, y);
+}
 
-        if bands >= 6 {
-            x += (CHAR_WIDTH * Band::get_width(&ValType::Tolerance) as i16) as f32 + spacing;
-        }
-        let tcr_index = Band::new(bands >= 6, ValType::TCR, x as i16, BAND_Xs[5]);
+// The same for any unit, e.g. 100nF
+fn draw_quantity(decimal: &Decimal, unit: Unit, x: i16, y: i16) {
+    let len = draw_number(decimal, unit, x, y);
+    draw_unit(unit, x + CHAR_WIDTH * len, y);
+}
 
-        Resistance {
-            value1,
-            value10,
-            value100,
-            multiplier_pow,
-            tolerance_index,
-            tcr_index,
+// Prints a value without its unit's sign, for where space is tight
+// Returns the number of characters printed
+fn draw_number(decimal: &Decimal, unit: Unit, x: i16, y: i16) -> i16 {
+    let formatted = Formatted::new(*decimal, unit.prefixes());
+    let len = formatted.len() as i16;
 
-            bands,
-        }
+    arduboy.set_cursor(x, y);
+    arduboy.print(formatted.without_prefix().as_str());
+    if let Some(prefix) = formatted.prefix() {
+        draw_prefix(prefix, x + CHAR_WIDTH * (len - 1), y);
     }
+    len
+}
 
-    // An easier way to index through the resistor correctly
-    fn index(&self, i: u8) -> &Band {
-        match self.bands {
-            3 => match i {
-                0 => return &self.value1,
-                1 => return &self.value10,
-                2 => return &self.multiplier_pow,
-                _ => panic!(),
-            },
-            4 => match i {
-                0 => &self.value1,
-                1 => &self.value10,
-                2 => &self.multiplier_pow,
-                3 => &self.tolerance_index,
-                _ => panic!(),
-            },
-            5 => match i {
-                0 => &self.value1,
-                1 => &self.value10,
-                2 => &self.value100,
-                3 => &self.multiplier_pow,
-                4 => &self.tolerance_index,
-                _ => panic!(),
-            },
-            6 => match i {
-                0 => &self.value1,
-                1 => &self.value10,
-                2 => &self.value100,
-                3 => &self.multiplier_pow,
-                4 => &self.tolerance_index,
-                5 => &self.tcr_index,
-                _ => panic!(),
-            },
-            _ => panic!(),
-        }
+// Prints an SI prefix, micro being a sprite as the font doesn't have it
+fn draw_prefix(prefix: &str, x: i16, y: i16) {
+    if prefix == "u\0" {
+        sprites::draw_override(x, y, get_sprite_addr!(Micro), 0);
+    } else {
+        arduboy.set_cursor(x, y);
+        arduboy.print(prefix);
     }
+}
 
-    fn index_mut(&mut self, i: u8) -> &mut Band {
-        match self.bands {
-            3 => match i {
-                0 => &mut self.value1,
-                1 => &mut self.value10,
-                2 => &mut self.multiplier_pow,
-                _ => panic!(),
-            },
-            4 => match i {
-                0 => &mut self.value1,
-                1 => &mut self.value10,
-                2 => &mut self.multiplier_pow,
-                3 => &mut self.tolerance_index,
-                _ => panic!(),
-            },
-            5 => match i {
-                0 => &mut self.value1,
-                1 => &mut self.value10,
-                2 => &mut self.value100,
-                3 => &mut self.multiplier_pow,
-                4 => &mut self.tolerance_index,
-                _ => panic!(),
-            },
-            6 => match i {
-                0 => &mut self.value1,
-                1 => &mut self.value10,
-                2 => &mut self.value100,
-                3 => &mut self.multiplier_pow,
-                4 => &mut self.tolerance_index,
-                5 => &mut self.tcr_index,
-                _ => panic!(),
-            },
-            _ => panic!(),
-        }
+// Prints the sign of a unit, the ohm sign being a sprite
+fn draw_unit(unit: Unit, x: i16, y: i16) {
+    if let Unit::Ohms = unit {
+        sprites::draw_override(x, y, get_sprite_addr!(Ohm), 0);
+    } else {
+        arduboy.set_cursor(x, y);
+        arduboy.print(unit.symbol());
     }
+}
 
-    fn display(&self) {
-        for place in 0..self.bands {
-            self.index(place).display();
-        }
+// Draws the digit and multiplier bands of a value side by side
+fn draw_bands(decimal: &Decimal, figures: u8, x: i16, y: i16) {
+    let mut place = 10u32.pow(figures as u32 - 1);
+    for i in 0..figures as i16 {
+        let digit = (decimal.sig / place % 10) as usize;
+        sprites::draw_override(
+            x + i * BANDS_GAP,
+            y,
+            get_sprite_addr!(Band),
+            rgb(VALUE_COLORS[digit]).3,
+        );
+        place /= 10;
     }
+    sprites::draw_override(
+        x + figures as i16 * BANDS_GAP,
+        y,
+        get_sprite_addr!(Band),
+        rgb(MULTIPLIER_COLORS[(decimal.pow - MIN_POW) as usize]).3,
+    );
+}
+
+// The resistor body, drawn over the bands so they only show where they wrap around it
+fn draw_body() {
+    sprites::draw_external_mask(
+        0,
+        RES_Y,
+        get_sprite_addr!(Res),
+        get_sprite_addr!(ResMask),
+        0,
+        0,
+    );
 }
 
 struct RGB(u8, u8, u8, u8, u8);
@@ -324,6 +351,11 @@ fn write_led(color: &RGB) {
     arduboy.set_rgb_led(color.0, color.1, color.2)
 }
 
+// How a band colour is lit and drawn
+fn rgb(colour: Colour) -> &'static RGB {
+    &COLOUR_RGBS[colour as usize]
+}
+
 // For colour selection menu
 fn draw_menu(band_type: &ValType, menu_index: u8) {
     arduboy.draw_rect(
@@ -341,26 +373,35 @@ fn draw_menu(band_type: &ValType, menu_index: u8) {
         Color::Black,
     );
 
-    let arr_len = Band::rgb_arr_from_valtype(&band_type).len();
+    let arr_len = Band::colours_from_valtype(band_type).len();
+    // Bands with letter codes show them next to each colour, which needs wider columns
+    let letters = letters(band_type);
+    let column = ABBR_WIDTH as i16 + MENU_GAP + if letters.is_some() { CHAR_WIDTH } else { 0 };
 
     let mut count: i16 = 0;
-    let x = (WIDTH - ABBR_WIDTH) as i16 / 2 - MENU_GAP - ABBR_WIDTH as i16;
+    let x = (WIDTH as i16 - 3 * column + MENU_GAP) / 2;
     let y =
         (HEIGHT as i16 - (arr_len as i16 + 2) / 3 * (ABBR_HEIGHT as i16 + MENU_GAP) + MENU_GAP) / 2;
 
-    for rgb in Band::rgb_arr_from_valtype(&band_type) {
+    for &colour in Band::colours_from_valtype(band_type) {
         let center_final = if count + 1 == arr_len as i16 && arr_len % 3 != 0 {
             1
         } else {
             0
         };
 
+        let swatch_x = x + ((count % 3) + center_final) * column;
+        let swatch_y = y + (count / 3) * (ABBR_HEIGHT as i16 + MENU_GAP);
         sprites::draw_override(
-            x + ((count % 3) + center_final) * (ABBR_WIDTH as i16 + MENU_GAP),
-            y + (count / 3) * (ABBR_HEIGHT as i16 + MENU_GAP),
+            swatch_x,
+            swatch_y,
             get_sprite_addr!(Abbreviations),
-            rgb.4,
+            rgb(colour).4,
         );
+        if let Some(letters) = letters {
+            arduboy.set_cursor(swatch_x + ABBR_WIDTH as i16 + 1, swatch_y - 1);
+            arduboy.print(letters[count as usize]);
+        }
 
         count += 1;
     }
@@ -373,18 +414,149 @@ fn draw_menu(band_type: &ValType, menu_index: u8) {
     };
 
     sprites::draw_override(
-        x + ((menu_index as i16 % 3) + centre_final) * (ABBR_WIDTH as i16 + MENU_GAP) - 4,
+        x + ((menu_index as i16 % 3) + centre_final) * column - 4,
         y + (menu_index as i16 / 3) * (ABBR_HEIGHT as i16 + MENU_GAP),
         get_sprite_addr!(Arrow),
         0,
     )
 }
 
+// Screens that can be picked from the mode menu, in menu order
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Colour,
+    Entry,
+    Series,
+    Range,
+    Drift,
+    Network,
+    Finder,
+    Divider,
+    Led,
+    Surface,
+    Capacitor,
+    Inductor,
+    Reverse,
+    Rkm,
+    Power,
+}
+
+const MODES: [Mode; 15] = [
+    Mode::Colour,
+    Mode::Entry,
+    Mode::Series,
+    Mode::Range,
+    Mode::Drift,
+    Mode::Network,
+    Mode::Finder,
+    Mode::Divider,
+    Mode::Led,
+    Mode::Surface,
+    Mode::Capacitor,
+    Mode::Inductor,
+    Mode::Reverse,
+    Mode::Rkm,
+    Mode::Power,
+];
+const MODE_NAMES: [&str; 15] = [
+    "Colour to value\0",
+    "Value to colour\0",
+    "Preferred values\0",
+    "Tolerance range\0",
+    "Temperature drift\0",
+    "Series/parallel\0",
+    "Find combination\0",
+    "Voltage divider\0",
+    "LED resistor\0",
+    "SMD code\0",
+    "Capacitor colour\0",
+    "Inductor colour\0",
+    "Reversed reading\0",
+    "RKM code\0",
+    "Power rating\0",
+];
+
+// For mode selection menu
+fn draw_modes(mode_index: u8) {
+    let height = MODE_ROWS * CHAR_HEIGHT as u8 + 4;
+    let y = (HEIGHT - height) as i16 / 2;
+
+    arduboy.draw_rect(
+        ((WIDTH - MODE_WIDTH - 2) / 2).into(),
+        y - 1,
+        MODE_WIDTH + 2,
+        height + 2,
+        Color::White,
+    );
+    arduboy.fill_rect(
+        ((WIDTH - MODE_WIDTH) / 2).into(),
+        y,
+        MODE_WIDTH,
+        height,
+        Color::Black,
+    );
+
+    // Scroll so the selected mode is always visible
+    let first = if mode_index >= MODE_ROWS {
+        mode_index + 1 - MODE_ROWS
+    } else {
+        0
+    };
+    let x = ((WIDTH - MODE_WIDTH) / 2) as i16 + MENU_GAP;
+
+    for row in 0..MODE_ROWS {
+        let index = (first + row) as usize;
+        if index >= MODE_NAMES.len() {
+            break;
+        }
+        arduboy.set_cursor(x, y + 2 + (row as i16) * CHAR_HEIGHT);
+        arduboy.print(MODE_NAMES[index]);
+    }
+
+    sprites::draw_override(
+        x - 5,
+        y + 3 + (mode_index - first) as i16 * CHAR_HEIGHT,
+        get_sprite_addr!(Arrow),
+        0,
+    )
+}
+
+// Lets another mode edit one of its resistors with the colour bands
+// The main resistor is put back once the mode menu is used to leave
+struct PartEditor {
+    stash: ColourCode,
+    owner: Option<Mode>,
+}
+
+impl PartEditor {
+    const fn new() -> Self {
+        PartEditor {
+            stash: ColourCode::new(DEFAULT_BANDS),
+            owner: None,
+        }
+    }
+
+    // Swaps the main resistor for the part
+    fn start(&mut self, owner: Mode, res: &mut Resistance, part: &ColourCode) {
+        self.stash = res.code();
+        *res = Resistance::from_code(part);
+        self.owner = Some(owner);
+    }
+
+    // Brings back the main resistor, returning the edited part
+    fn finish(&mut self, res: &mut Resistance) -> ColourCode {
+        let part = res.code();
+        *res = Resistance::from_code(&self.stash);
+        self.owner = None;
+        part
+    }
+}
+
 // EEPROM
 fn init_eeprom(eep: &EEPROMBYTECHECKLESS) -> u8 {
     eep.init();
     let saved_data = eep.read();
-    if let MIN_BANDS..=MAX_BANDS = saved_data {
+    if let JUMPER_BANDS | MIN_BANDS..=MAX_BANDS = saved_data {
         saved_data
     } else {
         DEFAULT_BANDS
@@ -411,34 +583,18 @@ const VIOLET: RGB = RGB(112, 0, 224, Patterns::Wavy as u8, 10);
 const GRAY: RGB = RGB(24, 24, 24, Patterns::Gray as u8, 11);
 const WHITE: RGB = RGB(255, 255, 255, Patterns::White as u8, 12);
 
-const VALUE_COLORS: [RGB; 10] = [
-    BLACK, BROWN, RED, ORANGE, YELLOW, GREEN, BLUE, VIOLET, GRAY, WHITE,
-];
-
-const MULTIPLIER_COLORS: [RGB; 13] = [
+// In the same order as Colour
+const COLOUR_RGBS: [RGB; 13] = [
     PINK, SILVER, GOLD, BLACK, BROWN, RED, ORANGE, YELLOW, GREEN, BLUE, VIOLET, GRAY, WHITE,
 ];
 
-const TOLERANCE_COLORS: [RGB; 10] = [
-    GRAY, YELLOW, ORANGE, VIOLET, BLUE, GREEN, BROWN, RED, GOLD, SILVER,
-];
-
-const TCR_COLORS: [RGB; 9] = [GRAY, VIOLET, BLUE, ORANGE, GREEN, YELLOW, RED, BROWN, BLACK];
-
-const TOLERANCES: [&str; 10] = [
-    "0.01\0", "0.02\0", "0.05\0", "0.10\0", "0.25\0", "0.50\0", "1.00\0", "2.00\0", "5.00\0",
-    "10.0\0",
-];
-const TCRs: [&str; 9] = [
-    "1\0", "5\0", "10\0", "15\0", "20\0", "25\0", "50\0", "100\0", "250\0",
-];
-
-const PREFIXES: [&str; 5] = ["m\0", " \0", "k\0", "M\0", "G\0"];
-
-const VALUES3_WIDTH: i16 = 7 * CHAR_WIDTH;
-const VALUES2_WIDTH: i16 = 6 * CHAR_WIDTH;
+const VALUES3_WIDTH: i16 = 8 * CHAR_WIDTH;
+const VALUES2_WIDTH: i16 = 7 * CHAR_WIDTH;
 const TOLERANCE_WIDTH: i16 = 5 * CHAR_WIDTH;
 const TCR_WIDTH: i16 = 7 * CHAR_WIDTH;
+const VOLTAGE_WIDTH: i16 = 4 * CHAR_WIDTH;
+const JUMPER_WIDTH: i16 = 9 * CHAR_WIDTH;
+const FAILURE_RATE_WIDTH: i16 = 7 * CHAR_WIDTH;
 
 const TEXT_WIDTHS: [i16; 4] = [VALUES3_WIDTH, VALUES2_WIDTH, TOLERANCE_WIDTH, TCR_WIDTH];
 
@@ -450,13 +606,19 @@ const RES_HEIGHT: u8 = 32;
 const MENU_SIZE: u8 = 56;
 const MENU_GAP: i16 = 6;
 
+const MODE_WIDTH: u8 = 112;
+const MODE_ROWS: u8 = 6;
+
 const ABBR_WIDTH: u8 = 7;
 const ABBR_HEIGHT: u8 = 5;
-const ABBR_Y: i16 = (HEIGHT as i16 + RES_Y + RES_HEIGHT as i16) / 2 - ABBR_HEIGHT as i16;
+const ABBR_Y: i16 = (VALUE_Y + RES_Y + RES_HEIGHT as i16 - ABBR_HEIGHT as i16) / 2;
+
+const VALUE_Y: i16 = HEIGHT as i16 - CHAR_HEIGHT;
 
 const BAND_Y: i16 = RES_Y;
 const BAND_Xs: [i16; 6] = [32, 44, 56, 69, 82, 94];
 const BAND_WIDTH: i16 = 6;
+const BANDS_GAP: i16 = BAND_WIDTH + 2;
 
 const EEPROM_ADDR: i16 = 416;
 
@@ -473,6 +635,12 @@ progmem!(
         0x44, 0x44, 0x5f, 0x44, 0x44,
     ];
 
+    #[link_section = ".progmem.data"]
+    static Micro: [u8; 7] = [
+        5, 8, // width, height,
+        0xfc, 0x40, 0x40, 0x20, 0x7c,
+    ];
+
     static Res: [u8; 514] = [
         128, 32, // width, height,
         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
@@ -623,24 +791,26 @@ enum Patterns {
 }
 
 //Initialize variables used in this game
-static mut pointer: u8 = 0;
-static mut menu_pointer: u8 = 0;
-static mut resistance: Resistance = Resistance::new(DEFAULT_BANDS);
-static mut show_menu: bool = false;
-
-const EEPROM_CONFIRM_TIME: u16 = 30;
-static mut eeprom_confirm_timer: u16 = 0;
-
-// Setup eeprom memory
-// EEPROMBYTECHECKLESS is a clone of the EEPROMBYTE struct without check digits
-static mut eeprom: EEPROMBYTECHECKLESS = EEPROMBYTECHECKLESS::new(EEPROM_ADDR - 16);
+static mut app: App = App::new();
+
+/// The buttons the game reads, for replaying or recording a script between frames
+///
+/// # Safety
+///
+/// The buttons are part of the game's state, so this must only be called between frames and the
+/// reference dropped before `setup` or `loop_` runs again.
+pub unsafe fn input() -> &'static mut Input {
+    (*addr_of_mut!(app)).input()
+}
 
 //The setup() function runs once when you turn your Arduboy on
 #[no_mangle]
 pub unsafe extern "C" fn setup() {
     // put your setup code here, to run once:
     arduboy.begin();
-    resistance = Resistance::new(init_eeprom(&eeprom));
+    let state = &mut *addr_of_mut!(app);
+    *state = App::new();
+    state.load();
     arduboy.clear();
     arduboy.set_frame_rate(30);
 }
@@ -656,153 +826,15 @@ pub unsafe extern "C" fn loop_() {
 
     arduboy.clear();
 
-    // CONTROLS
-
-    arduboy.poll_buttons();
-
-    let current_rgb = Band::rgb_arr_from_valtype(&resistance.index(pointer).vtype);
-
-    if !show_menu {
-        if A.just_pressed() {
-            menu_pointer = resistance.index_mut(pointer).get_pointer() as u8;
-            show_menu = true;
-        }
-        if B.just_pressed() {
-            if LEFT.pressed() && RIGHT.pressed() {
-                // Save default bands button combo
-                save_eeprom(&eeprom, resistance.bands);
-                eeprom_confirm_timer = EEPROM_CONFIRM_TIME;
-            } else {
-                // Stick the pointer to currently selected band
-                if resistance.bands == 4 && pointer > 1 {
-                    pointer += 1;
-                }
-
-                // Increment, looping at 6 back to 3
-                if resistance.bands < MAX_BANDS {
-                    resistance = Resistance::new(resistance.bands + 1);
-                } else {
-                    resistance = Resistance::new(MIN_BANDS);
-                }
-
-                // Prevent invalid index call
-                if pointer > resistance.bands - 1 {
-                    pointer = resistance.bands - 1;
-                }
-            }
-        }
-
-        if LEFT.just_pressed() {
-            if pointer > 0 {
-                pointer -= 1;
-            }
-        }
-        if RIGHT.just_pressed() {
-            if pointer < resistance.bands - 1 {
-                pointer += 1;
-            }
-        }
-        if UP.just_pressed() {
-            resistance.index_mut(pointer).change_by(1);
-        }
-        if DOWN.just_pressed() {
-            resistance.index_mut(pointer).change_by(-1);
-        }
+    let state = &mut *addr_of_mut!(app);
 
-        resistance.index(pointer).display_rgb();
-    } else {
-        // Select colour choice
-        if A.just_pressed() {
-            resistance.index_mut(pointer).change_to(menu_pointer as i8);
-            show_menu = false;
-        }
-        // Cancel menu
-        if B.just_pressed() {
-            show_menu = false;
-        }
-
-        // Move menu cursor left if possible
-        if LEFT.just_pressed() && menu_pointer % 3 != 0 {
-            if menu_pointer > 0 {
-                menu_pointer -= 1;
-            }
-        }
-        // Move menu cursor right if possible
-        if RIGHT.just_pressed() {
-            if menu_pointer < (current_rgb.len() - 1) as u8 && menu_pointer % 3 != 2 {
-                menu_pointer += 1;
-            }
-        }
-        if UP.just_pressed() {
-            if menu_pointer > 2 {
-                // If pointing to central bottom place, cursor will go directly up
-                if menu_pointer == (current_rgb.len() - 1) as u8 && current_rgb.len() % 3 != 0 {
-                    menu_pointer -= 2;
-                } else {
-                    menu_pointer -= 3;
-                }
-            }
-        }
-        if DOWN.just_pressed() {
-            if menu_pointer < (current_rgb.len() - 3) as u8 {
-                menu_pointer += 3;
-            } else if current_rgb.len() % 3 != 0 {
-                menu_pointer = (current_rgb.len() - 1) as u8
-            }
-        }
-
-        write_led(&current_rgb[menu_pointer as usize])
-    }
+    // CONTROLS
 
-    // LED flashes to confirm EEPROM write
-    if eeprom_confirm_timer > 0 {
-        eeprom_confirm_timer -= 1;
-        arduboy.set_rgb_led(96, 255, 16)
-    }
+    state.update();
 
     // DISPLAY
 
-    // Increase width of selected band
-    arduboy.draw_fast_vline(
-        resistance.index(pointer).bandx - 1,
-        RES_Y,
-        RES_HEIGHT,
-        Color::White,
-    );
-    arduboy.draw_fast_vline(
-        resistance.index(pointer).bandx + BAND_WIDTH,
-        RES_Y,
-        RES_HEIGHT,
-        Color::White,
-    );
-    // Display all bands
-    resistance.display();
-
-    // Draw resistor over bands
-    sprites::draw_external_mask(
-        0,
-        RES_Y,
-        get_sprite_addr!(Res),
-        get_sprite_addr!(ResMask),
-        0,
-        0,
-    );
-
-    // Underline selected band text
-    arduboy.draw_fast_hline(
-        resistance.index(pointer).x - 1,
-        TEXT_Y + CHAR_HEIGHT,
-        resistance.index(pointer).width * CHAR_WIDTH as u8 + 1,
-        Color::White,
-    );
-
-    // Draw menu
-    if show_menu {
-        draw_menu(&resistance.index(pointer).vtype, menu_pointer);
-    }
-
-// Draw border
-    arduboy.draw_rect(0, 0, WIDTH, HEIGHT, Color::White);
+    state.display();
 
     arduboy.display();
 }
@@ -0,0 +1,187 @@
+// Series and parallel mode, combining several resistors into one equivalent resistance
+
+use crate::*;
+
+const MAX_PARTS: usize = 4;
+
+const ROWS_Y: i16 = 13;
+const TOTAL_Y: i16 = 46;
+
+const NUMBER_X: i16 = 9;
+const PART_X: i16 = NUMBER_X + CHAR_WIDTH * 2;
+
+// Results are rounded to keep them on screen
+const TOTAL_FIGURES: u8 = 4;
+
+pub struct Network {
+    parts: [ColourCode; MAX_PARTS],
+    count: u8,
+    parallel: bool,
+    cursor: u8,
+}
+
+impl Network {
+    pub const fn new() -> Self {
+        Network {
+            parts: [ColourCode::new(DEFAULT_BANDS); MAX_PARTS],
+            count: 0,
+            parallel: false,
+            cursor: 0,
+        }
+    }
+
+    // Returns true when the screen should be closed, including to edit a part
+    pub fn update(&mut self, input: &Input, res: &mut Resistance, editor: &mut PartEditor) -> bool {
+        if input.just_pressed(B) {
+            return true;
+        }
+
+        if input.just_pressed(A) {
+            if input.pressed(LEFT) && input.pressed(RIGHT) {
+                // Remove part button combo
+                self.remove();
+            } else {
+                if self.cursor == self.count {
+                    // New parts start as a copy of the main resistor
+                    self.parts[self.count as usize] = res.code();
+                    self.count += 1;
+                }
+                editor.start(Mode::Network, res, &self.parts[self.cursor as usize]);
+                return true;
+            }
+        }
+
+        if input.just_pressed(LEFT) || input.just_pressed(RIGHT) {
+            self.parallel = !self.parallel;
+        }
+
+        // The row after the last part adds a new one
+        let rows = if (self.count as usize) < MAX_PARTS {
+            self.count + 1
+        } else {
+            self.count
+        };
+        if input.just_pressed(UP) && self.cursor > 0 {
+            self.cursor -= 1;
+        }
+        if input.just_pressed(DOWN) && self.cursor < rows - 1 {
+            self.cursor += 1;
+        }
+
+        false
+    }
+
+    // Takes back the part once it has been edited
+    pub fn store(&mut self, part: ColourCode) {
+        self.parts[self.cursor as usize] = part;
+    }
+
+    pub fn part_number(&self) -> i16 {
+        self.cursor as i16 + 1
+    }
+
+    fn remove(&mut self) {
+        if self.cursor >= self.count {
+            return;
+        }
+        for i in self.cursor as usize..self.count as usize - 1 {
+            self.parts[i] = self.parts[i + 1];
+        }
+        self.count -= 1;
+    }
+
+    // Equivalent nominal, lowest and highest resistance in milliohms
+    // Combining the parts' own bounds gives the overall bounds, as both kinds grow with each part
+    fn total(&self) -> Option<(Milliohms, Milliohms, Milliohms)> {
+        let mut total: Option<(Milliohms, Milliohms, Milliohms)> = None;
+
+        for part in self.parts[..self.count as usize].iter() {
+            let res = Resistance::from_code(part);
+            let nominal = res.ohms();
+            let (min, max) = res.bounds();
+
+            total = Some(match total {
+                None => (nominal, min, max),
+                Some((total_nominal, total_min, total_max)) => {
+                    if self.parallel {
+                        (
+                            parallel(total_nominal, nominal),
+                            parallel(total_min, min),
+                            parallel(total_max, max),
+                        )
+                    } else {
+                        (total_nominal + nominal, total_min + min, total_max + max)
+                    }
+                }
+            });
+        }
+
+        total
+    }
+
+    pub fn display(&self) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        if self.parallel {
+            arduboy.print(f!(b"< Parallel >\0"));
+        } else {
+            arduboy.print(f!(b"< Series >\0"));
+        }
+
+        for row in 0..=self.count {
+            let y = ROWS_Y + row as i16 * CHAR_HEIGHT;
+            if row as usize >= MAX_PARTS {
+                break;
+            }
+
+            arduboy.set_cursor(NUMBER_X, y);
+            if row == self.count {
+                arduboy.print(f!(b"+ Add part\0"));
+                break;
+            }
+
+            let res = Resistance::from_code(&self.parts[row as usize]);
+            let value = res.decimal();
+            arduboy.print(row as i16 + 1);
+            draw_value(&value, PART_X, y);
+
+            let x = PART_X + value_width(&value) + CHAR_WIDTH;
+            sprites::draw_override(x, y, get_sprite_addr!(Plus_Minus), 0);
+            arduboy.set_cursor(x + CHAR_WIDTH, y);
+            arduboy.print(res.tolerance_str());
+            arduboy.print(f!(b"%\0"));
+        }
+
+        sprites::draw_override(
+            3,
+            ROWS_Y + 1 + self.cursor as i16 * CHAR_HEIGHT,
+            get_sprite_addr!(Arrow),
+            0,
+        );
+
+        arduboy.set_cursor(CHAR_WIDTH, TOTAL_Y);
+        let (nominal, min, max) = match self.total() {
+            Some(total) => total,
+            None => {
+                arduboy.print(f!(b"No parts\0"));
+                return;
+            }
+        };
+        arduboy.print(f!(b"Total: \0"));
+        draw_value(
+            &Decimal::from_milliohms(nominal).round(TOTAL_FIGURES),
+            CHAR_WIDTH * 8,
+            TOTAL_Y,
+        );
+
+        let min = Decimal::from_milliohms(min).round(TOTAL_FIGURES);
+        draw_value(&min, CHAR_WIDTH, VALUE_Y);
+        let x = CHAR_WIDTH + value_width(&min);
+        arduboy.set_cursor(x, VALUE_Y);
+        arduboy.print(f!(b"-\0"));
+        draw_value(
+            &Decimal::from_milliohms(max).round(TOTAL_FIGURES),
+            x + CHAR_WIDTH,
+            VALUE_Y,
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Power rating mode, showing the most voltage and current the resistor can take at a wattage
+
+use crate::*;
+
+// Typical sizes of carbon film resistors, with the highest voltage their body is made for
+const RATING_NAMES: [&str; 5] = ["1/8W\0", "1/4W\0", "1/2W\0", "1W\0", "2W\0"];
+const RATING_MILLIWATTS: [u64; 5] = [125, 250, 500, 1000, 2000];
+const BODY_SIZES: [&str; 5] = [
+    "3.3x1.8mm\0",
+    "6.3x2.4mm\0",
+    "9x3.2mm\0",
+    "11x4.5mm\0",
+    "15x5mm\0",
+];
+const MAX_VOLTS: [u64; 5] = [200, 250, 350, 500, 500];
+
+const BODY_X: i16 = CHAR_WIDTH * 8;
+const RESISTOR_Y: i16 = 15;
+const VOLTS_Y: i16 = 26;
+const AMPS_Y: i16 = 35;
+const LIMIT_Y: i16 = 45;
+const VALUE_X: i16 = CHAR_WIDTH * 10;
+
+// Results are rounded to keep them on screen
+const FIGURES: u8 = 3;
+
+pub struct Power {
+    rating: u8,
+}
+
+impl Power {
+    pub const fn new() -> Self {
+        Power { rating: 1 }
+    }
+
+    // Returns true when the screen should be closed
+    pub fn update(&mut self, input: &Input) -> bool {
+        if input.just_pressed(B) {
+            return true;
+        }
+
+        if (input.just_pressed(LEFT) || input.just_pressed(DOWN)) && self.rating > 0 {
+            self.rating -= 1;
+        }
+        if (input.just_pressed(RIGHT) || input.just_pressed(UP))
+            && self.rating < RATING_NAMES.len() as u8 - 1
+        {
+            self.rating += 1;
+        }
+
+        false
+    }
+
+    // Most voltage across the part in millivolts and current through it in microamps, and whether
+    // the voltage is held back by the body's rating rather than the power
+    fn limits(&self, milliohms: Milliohms) -> (u64, u64, bool) {
+        let milliwatts = RATING_MILLIWATTS[self.rating as usize];
+        let max_millivolts = MAX_VOLTS[self.rating as usize] * 1000;
+
+        // V = √(PR), so mV = √(mW × mΩ)
+        let millivolts = isqrt(milliwatts.saturating_mul(milliohms));
+        if millivolts > max_millivolts {
+            // I = V / R
+            let microamps = (max_millivolts as u128 * 1_000_000 / milliohms as u128) as u64;
+            (max_millivolts, microamps, true)
+        } else {
+            // I = √(P / R), so µA = √(mW × 10¹² / mΩ)
+            let microamps = isqrt(milliwatts * 1_000_000_000_000 / milliohms);
+            (millivolts, microamps, false)
+        }
+    }
+
+    pub fn display(&self, res: &Resistance) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        arduboy.print(f!(b"<\0"));
+        arduboy.print(RATING_NAMES[self.rating as usize]);
+        arduboy.print(f!(b">\0"));
+        arduboy.set_cursor(BODY_X, TEXT_Y);
+        arduboy.print(BODY_SIZES[self.rating as usize]);
+
+        arduboy.set_cursor(CHAR_WIDTH, RESISTOR_Y);
+        arduboy.print(f!(b"Resistor\0"));
+        draw_value(&res.decimal(), VALUE_X, RESISTOR_Y);
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        arduboy.print(f!(b"L/R:Rating  B:Back\0"));
+
+        let milliohms = res.ohms();
+        if milliohms == 0 {
+            arduboy.set_cursor(CHAR_WIDTH, VOLTS_Y);
+            arduboy.print(f!(b"No limit at 0\0"));
+            sprites::draw_override(CHAR_WIDTH * 14, VOLTS_Y, get_sprite_addr!(Ohm), 0);
+            return;
+        }
+        let (millivolts, microamps, limited) = self.limits(milliohms);
+
+        arduboy.set_cursor(CHAR_WIDTH, VOLTS_Y);
+        arduboy.print(f!(b"Max V\0"));
+        draw_quantity(
+            &Decimal::from_milli(millivolts).round(FIGURES),
+            Unit::Volts,
+            VALUE_X,
+            VOLTS_Y,
+        );
+
+        arduboy.set_cursor(CHAR_WIDTH, AMPS_Y);
+        arduboy.print(f!(b"Max I\0"));
+        draw_quantity(
+            &Decimal::new(microamps as u32, -6).round(FIGURES),
+            Unit::Amps,
+            VALUE_X,
+            AMPS_Y,
+        );
+
+        if limited {
+            arduboy.set_cursor(CHAR_WIDTH, LIMIT_Y);
+            arduboy.print(f!(b"Limited by voltage\0"));
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// Preferred values mode, comparing the resistance against an E-series
+
+use crate::*;
+
+const CHECK_Y: i16 = 16;
+const BELOW_Y: i16 = 28;
+const ABOVE_Y: i16 = 38;
+
+// Returns true when the screen should be closed
+pub fn update(input: &Input, res: &mut Resistance, series: &mut Series) -> bool {
+    if input.just_pressed(B) {
+        return true;
+    }
+    // Snap to the closest preferred value
+    if input.just_pressed(A) {
+        if let Some(nearest) = series.nearest(res.decimal()) {
+            let _ = res.set_decimal(nearest);
+        }
+    }
+
+    let index = *series as usize;
+    if input.just_pressed(LEFT) && index > 0 {
+        *series = SERIES[index - 1];
+    }
+    if input.just_pressed(RIGHT) && index < SERIES.len() - 1 {
+        *series = SERIES[index + 1];
+    }
+    if input.just_pressed(UP) {
+        res.step_series(*series, true);
+    }
+    if input.just_pressed(DOWN) {
+        res.step_series(*series, false);
+    }
+
+    false
+}
+
+pub fn display(res: &Resistance, series: Series) {
+    let value = res.decimal();
+
+    arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+    arduboy.print(f!(b"Series: \0"));
+    arduboy.print(series.name());
+
+    draw_value(&value, CHAR_WIDTH, CHECK_Y);
+    arduboy.set_cursor(CHAR_WIDTH + value_width(&value), CHECK_Y);
+    if series.contains(value) {
+        arduboy.print(f!(b" in \0"));
+    } else {
+        arduboy.print(f!(b" not in \0"));
+    }
+    arduboy.print(series.name());
+
+    arduboy.set_cursor(CHAR_WIDTH, BELOW_Y);
+    arduboy.print(f!(b"Below: \0"));
+    match series.below(value) {
+        Some(below) => draw_value(&below, CHAR_WIDTH * 8, BELOW_Y),
+        None => arduboy.print(f!(b"-\0")),
+    }
+
+    arduboy.set_cursor(CHAR_WIDTH, ABOVE_Y);
+    arduboy.print(f!(b"Above: \0"));
+    match series.above(value) {
+        Some(above) => draw_value(&above, CHAR_WIDTH * 8, ABOVE_Y),
+        None => arduboy.print(f!(b"-\0")),
+    }
+
+    arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+    arduboy.print(f!(b"A:Snap  B:Back\0"));
+}
@@ -0,0 +1,59 @@
+// Tolerance range mode, showing the absolute bounds the resistance could be within
+
+use crate::*;
+
+const TOLERANCE_Y: i16 = 16;
+const MIN_Y: i16 = 28;
+const MAX_Y: i16 = 38;
+const WARNING_Y: i16 = 47;
+
+// Bounds are rounded to keep them on screen
+const BOUND_FIGURES: u8 = 4;
+
+pub fn display(res: &Resistance) {
+    let nominal = res.decimal();
+    arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+    arduboy.print(f!(b"Nominal: \0"));
+    draw_value(&nominal, CHAR_WIDTH * 10, TEXT_Y);
+
+    arduboy.set_cursor(CHAR_WIDTH, TOLERANCE_Y);
+    arduboy.print(f!(b"Tolerance: \0"));
+    sprites::draw_override(
+        CHAR_WIDTH * 12,
+        TOLERANCE_Y,
+        get_sprite_addr!(Plus_Minus),
+        0,
+    );
+    arduboy.set_cursor(CHAR_WIDTH * 13, TOLERANCE_Y);
+    arduboy.print(res.tolerance_str());
+    arduboy.print(f!(b"%\0"));
+
+    let (min, max) = res.bounds();
+
+    arduboy.set_cursor(CHAR_WIDTH, MIN_Y);
+    arduboy.print(f!(b"Min: \0"));
+    draw_value(
+        &Decimal::from_milliohms(min).round(BOUND_FIGURES),
+        CHAR_WIDTH * 6,
+        MIN_Y,
+    );
+
+    arduboy.set_cursor(CHAR_WIDTH, MAX_Y);
+    arduboy.print(f!(b"Max: \0"));
+    draw_value(
+        &Decimal::from_milliohms(max).round(BOUND_FIGURES),
+        CHAR_WIDTH * 6,
+        MAX_Y,
+    );
+
+    // Values that aren't made at this tolerance suggest a misread band
+    if let Some(series) = res.unexpected_series() {
+        arduboy.set_cursor(CHAR_WIDTH, WARNING_Y);
+        arduboy.print(f!(b"Not \0"));
+        arduboy.print(series.name());
+        arduboy.print(f!(b", misread?\0"));
+    }
+
+    arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+    arduboy.print(f!(b"B:Back\0"));
+}
@@ -0,0 +1,69 @@
+// Reversed reading mode, checking whether the bands also make sense read from the other end
+
+use crate::*;
+
+const READ_Y: i16 = TEXT_Y;
+const REVERSED_Y: i16 = 24;
+const VERDICT_Y: i16 = 44;
+const VALUE_X: i16 = CHAR_WIDTH * 10;
+
+// Returns true when the screen should be closed
+pub fn update(input: &Input, res: &mut Resistance) -> bool {
+    if input.just_pressed(B) {
+        return true;
+    }
+    // Turn the resistor round
+    if input.just_pressed(A) {
+        if let Some(reversed) = res.reversed() {
+            *res = reversed;
+        }
+    }
+
+    false
+}
+
+pub fn display(res: &Resistance) {
+    arduboy.set_cursor(CHAR_WIDTH, READ_Y);
+    arduboy.print(f!(b"As read\0"));
+    draw_reading(res, READ_Y);
+
+    arduboy.set_cursor(CHAR_WIDTH, REVERSED_Y);
+    arduboy.print(f!(b"Reversed\0"));
+    let reversed = match res.reversed() {
+        Some(reversed) => reversed,
+        None => {
+            arduboy.set_cursor(VALUE_X, REVERSED_Y);
+            arduboy.print(f!(b"Invalid\0"));
+            arduboy.set_cursor(CHAR_WIDTH, VERDICT_Y);
+            arduboy.print(f!(b"Only reads one way\0"));
+            arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+            arduboy.print(f!(b"B:Back\0"));
+            return;
+        }
+    };
+    draw_reading(&reversed, REVERSED_Y);
+
+    arduboy.set_cursor(CHAR_WIDTH, VERDICT_Y);
+    let read = res.plausibility();
+    let flipped = reversed.plausibility();
+    if read > flipped {
+        arduboy.print(f!(b"Likely as read\0"));
+    } else if flipped > read {
+        arduboy.print(f!(b"Likely reversed\0"));
+    } else {
+        arduboy.print(f!(b"Could be either\0"));
+    }
+
+    arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+    arduboy.print(f!(b"A:Flip  B:Back\0"));
+}
+
+// Value with its tolerance underneath
+fn draw_reading(res: &Resistance, y: i16) {
+    draw_value(&res.decimal(), VALUE_X, y);
+    let y = y + CHAR_HEIGHT + 1;
+    sprites::draw_override(VALUE_X, y, get_sprite_addr!(Plus_Minus), 0);
+    arduboy.set_cursor(VALUE_X + CHAR_WIDTH, y);
+    arduboy.print(res.tolerance_str());
+    arduboy.print(f!(b"%\0"));
+}
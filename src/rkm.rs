@@ -0,0 +1,140 @@
+// RKM code mode, reading and writing values the way schematics print them, e.g. 4k7J
+
+use crate::*;
+
+const RKM_LEN: usize = 5;
+
+// Characters each place of the code cycles through, the tolerance letters coming last
+const RKM_CHARS: &[u8] = b" 0123456789RmkMGLPWBCDFJK";
+
+const CODE_X: i16 = CHAR_WIDTH * 6;
+const DECODED_X: i16 = CHAR_WIDTH * 13;
+const TOLERANCE_Y: i16 = 14;
+const RESISTOR_Y: i16 = 28;
+const RESISTOR_X: i16 = CHAR_WIDTH * 10;
+
+pub struct Rkm {
+    chars: [u8; RKM_LEN + 1],
+    cursor: u8,
+}
+
+impl Rkm {
+    pub const fn new() -> Self {
+        Rkm {
+            chars: *b"4k7J \0",
+            cursor: 0,
+        }
+    }
+
+    // The typed value, with the index of its tolerance in TOLERANCES if it has a letter
+    fn parse(&self) -> Result<(Decimal, Option<usize>), ValueError> {
+        let (decimal, letter) = parse_rkm(&self.chars[..RKM_LEN], &PREFIXES)?;
+        match letter {
+            // ±20% is what 3 band resistors already are
+            Some(letter) if letter == NO_BAND_TOLERANCE_LETTER.as_bytes()[0] => Ok((decimal, None)),
+            Some(letter) => {
+                let tolerance =
+                    colour_from_letter(&ValType::Tolerance, letter).ok_or(ValueError::Malformed)?;
+                Ok((decimal, Some(tolerance)))
+            }
+            None => Ok((decimal, None)),
+        }
+    }
+
+    // Returns true when the screen should be closed
+    pub fn update(&mut self, input: &Input, res: &mut Resistance) -> bool {
+        if input.just_pressed(A) {
+            if let Ok((decimal, tolerance)) = self.parse() {
+                if res.set_decimal(decimal).is_ok() {
+                    // 3 band resistors have nowhere to put the tolerance
+                    let band = &mut res.tolerance_index;
+                    if let (Some(tolerance), ValType::Tolerance, true) =
+                        (tolerance, &band.vtype, band.show)
+                    {
+                        band.value = tolerance as i8;
+                    }
+                    return true;
+                }
+            }
+        }
+        if input.just_pressed(B) {
+            return true;
+        }
+
+        if input.just_pressed(LEFT) && self.cursor > 0 {
+            self.cursor -= 1;
+        }
+        if input.just_pressed(RIGHT) && self.cursor < RKM_LEN as u8 - 1 {
+            self.cursor += 1;
+        }
+
+        let change = if input.just_pressed(UP) {
+            1
+        } else if input.just_pressed(DOWN) {
+            -1
+        } else {
+            0
+        };
+        if change != 0 {
+            let place = self.cursor as usize;
+            let index = RKM_CHARS
+                .iter()
+                .position(|&c| c == self.chars[place])
+                .unwrap_or(0) as i8;
+            let index = (index + change).rem_euclid(RKM_CHARS.len() as i8);
+            self.chars[place] = RKM_CHARS[index as usize];
+        }
+
+        false
+    }
+
+    pub fn display(&self, res: &Resistance) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        arduboy.print(f!(b"Code\0"));
+        arduboy.set_cursor(CODE_X, TEXT_Y);
+        arduboy.print(core::str::from_utf8(&self.chars).unwrap_or("\0"));
+        arduboy.draw_fast_hline(
+            CODE_X + self.cursor as i16 * CHAR_WIDTH - 1,
+            TEXT_Y + CHAR_HEIGHT,
+            CHAR_WIDTH as u8 + 1,
+            Color::White,
+        );
+
+        let parsed = self.parse();
+        arduboy.set_cursor(CODE_X + CHAR_WIDTH * RKM_LEN as i16, TEXT_Y);
+        arduboy.print(f!(b"=\0"));
+        match parsed {
+            Ok((decimal, tolerance)) => {
+                draw_value(&decimal, DECODED_X, TEXT_Y);
+                if let Some(tolerance) = tolerance {
+                    sprites::draw_override(DECODED_X, TOLERANCE_Y, get_sprite_addr!(Plus_Minus), 0);
+                    arduboy.set_cursor(DECODED_X + CHAR_WIDTH, TOLERANCE_Y);
+                    arduboy.print(TOLERANCES[tolerance]);
+                    arduboy.print(f!(b"%\0"));
+                }
+            }
+            Err(_) => {
+                arduboy.set_cursor(DECODED_X, TEXT_Y);
+                arduboy.print(f!(b"Invalid\0"));
+            }
+        }
+
+        // The main resistor written the same way
+        arduboy.set_cursor(CHAR_WIDTH, RESISTOR_Y);
+        arduboy.print(f!(b"Resistor\0"));
+        arduboy.set_cursor(RESISTOR_X, RESISTOR_Y);
+        arduboy.print(Formatted::rkm(res.decimal(), &PREFIXES).as_str());
+        arduboy.print(res.tolerance_letter());
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        match parsed.and_then(|(decimal, _)| res.fit(decimal)) {
+            Ok(_) => arduboy.print(f!(b"A:Set  B:Back\0")),
+            Err(ValueError::TooPrecise) => {
+                arduboy.print(f!(b"Not exact at \0"));
+                arduboy.print(res.bands as i16);
+                arduboy.print(f!(b" bands\0"));
+            }
+            Err(_) => arduboy.print(f!(b"B:Back\0")),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Surface mount mode, reading a marking and showing the main resistor's own markings
+
+use crate::smd::*;
+use crate::*;
+
+// Characters each place of the marking cycles through
+const CODE_CHARS: &[u8] = b" 0123456789RABCDEFHSXYZ";
+
+const CODE_X: i16 = CHAR_WIDTH * 6;
+const DECODED_X: i16 = CHAR_WIDTH * 12;
+
+const RESISTOR_Y: i16 = 16;
+const MARKINGS_Y: i16 = 25;
+const MARKINGS_X: i16 = CHAR_WIDTH * 10;
+const ROW_HEIGHT: i16 = 9;
+
+const MARKING_NAMES: [&str; 3] = ["3 digit\0", "4 digit\0", "EIA-96\0"];
+
+pub struct Surface {
+    code: Code,
+    cursor: u8,
+}
+
+impl Surface {
+    pub const fn new() -> Self {
+        Surface {
+            code: Code::new(*b"472 "),
+            cursor: 0,
+        }
+    }
+
+    // Returns true when the screen should be closed
+    pub fn update(&mut self, input: &Input, res: &mut Resistance) -> bool {
+        if input.just_pressed(A) {
+            if let Ok(decimal) = self.code.decode() {
+                if res.set_decimal(decimal).is_ok() {
+                    return true;
+                }
+            }
+        }
+        if input.just_pressed(B) {
+            return true;
+        }
+
+        if input.just_pressed(LEFT) && self.cursor > 0 {
+            self.cursor -= 1;
+        }
+        if input.just_pressed(RIGHT) && self.cursor < CODE_LEN as u8 - 1 {
+            self.cursor += 1;
+        }
+
+        let change = if input.just_pressed(UP) {
+            1
+        } else if input.just_pressed(DOWN) {
+            -1
+        } else {
+            0
+        };
+        if change != 0 {
+            let place = self.cursor as usize;
+            let index = CODE_CHARS
+                .iter()
+                .position(|&c| c == self.code.get(place))
+                .unwrap_or(0) as i8;
+            let index = (index + change).rem_euclid(CODE_CHARS.len() as i8);
+            self.code.set(place, CODE_CHARS[index as usize]);
+        }
+
+        false
+    }
+
+    pub fn display(&self, res: &Resistance) {
+        arduboy.set_cursor(CHAR_WIDTH, TEXT_Y);
+        arduboy.print(f!(b"Code\0"));
+        arduboy.set_cursor(CODE_X, TEXT_Y);
+        arduboy.print(self.code.as_str());
+        arduboy.draw_fast_hline(
+            CODE_X + self.cursor as i16 * CHAR_WIDTH - 1,
+            TEXT_Y + CHAR_HEIGHT,
+            CHAR_WIDTH as u8 + 1,
+            Color::White,
+        );
+
+        let decoded = self.code.decode();
+        arduboy.set_cursor(CODE_X + CHAR_WIDTH * CODE_LEN as i16, TEXT_Y);
+        arduboy.print(f!(b"=\0"));
+        let other = self.code.eia96_reading();
+        match (decoded, other) {
+            // Both readings, as R notation and EIA-96 disagree on codes like 02R
+            (Ok(decimal), Some(other)) => {
+                let len = draw_number(&decimal, Unit::Ohms, DECODED_X, TEXT_Y);
+                arduboy.print(f!(b"/\0"));
+                draw_value(&other, DECODED_X + (len + 1) * CHAR_WIDTH, TEXT_Y);
+            }
+            (Ok(decimal), None) => draw_value(&decimal, DECODED_X, TEXT_Y),
+            (Err(_), _) => {
+                arduboy.set_cursor(DECODED_X, TEXT_Y);
+                arduboy.print(f!(b"Invalid\0"));
+            }
+        }
+
+        // The main resistor written each way
+        let value = res.decimal();
+        arduboy.set_cursor(CHAR_WIDTH, RESISTOR_Y);
+        arduboy.print(f!(b"Resistor\0"));
+        draw_value(&value, MARKINGS_X, RESISTOR_Y);
+
+        for (i, marking) in MARKINGS.iter().enumerate() {
+            let y = MARKINGS_Y + i as i16 * ROW_HEIGHT;
+            arduboy.set_cursor(CHAR_WIDTH, y);
+            arduboy.print(MARKING_NAMES[i]);
+            arduboy.set_cursor(MARKINGS_X, y);
+            match Code::encode(value, *marking) {
+                Ok(code) => arduboy.print(code.as_str()),
+                Err(_) => arduboy.print(f!(b"-\0")),
+            }
+        }
+
+        arduboy.set_cursor(CHAR_WIDTH, VALUE_Y);
+        match decoded.and_then(|decimal| res.fit(decimal)) {
+            // A sets the first of the readings
+            Ok(_) if other.is_some() => arduboy.print(f!(b"A:Set 1st  B:Back\0")),
+            Ok(_) => arduboy.print(f!(b"A:Set  B:Back\0")),
+            Err(ValueError::TooPrecise) => {
+                arduboy.print(f!(b"Not exact at \0"));
+                arduboy.print(res.bands as i16);
+                arduboy.print(f!(b" bands\0"));
+            }
+            Err(_) => arduboy.print(f!(b"B:Back\0")),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// Helpers shared by the tests that run the game with the mock Arduboy
+
+use arduboy_mock::host;
+use std::path::PathBuf;
+use std::{env, fs};
+
+// Where the game keeps its number of bands, EEPROM_ADDR in the game
+pub const BANDS_ADDRESS: usize = 416;
+
+// The LED colour and the screen, as the text of a golden
+pub fn capture() -> String {
+    let (red, green, blue) = host::led();
+    format!(
+        "LED {} {} {}\n{}",
+        red,
+        green,
+        blue,
+        host::screen().to_ascii()
+    )
+}
+
+// Compares with the golden, or replaces it when updating
+pub fn check(name: &str, actual: &str, failures: &mut Vec<String>) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots", name]
+        .iter()
+        .collect();
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    match fs::read_to_string(&path) {
+        Ok(golden) if golden == actual => {}
+        Ok(golden) => {
+            let line = golden
+                .lines()
+                .zip(actual.lines())
+                .position(|(golden, actual)| golden != actual)
+                .unwrap_or(golden.lines().count().min(actual.lines().count()));
+            failures.push(format!("{} differs from line {}", name, line + 1));
+        }
+        Err(_) => failures.push(format!("{} has no golden", name)),
+    }
+}
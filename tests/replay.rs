@@ -0,0 +1,60 @@
+// Replays the scripts in tests/scripts through the game's input and compares the screen at the
+// end with the goldens in tests/snapshots, to catch changes to how the menus are navigated
+// Run with UPDATE_SNAPSHOTS=1 to write the goldens again after a deliberate change
+
+mod common;
+
+use arduboy_mock::host;
+use common::*;
+use game::input::Input;
+
+// Each script starts from a newly set up game, so they can be added in any order
+const SCRIPTS: [(&str, &str); 5] = [
+    ("colour_menu", include_str!("scripts/colour_menu.txt")),
+    (
+        "mode_menu_cancel",
+        include_str!("scripts/mode_menu_cancel.txt"),
+    ),
+    ("mode_menu", include_str!("scripts/mode_menu.txt")),
+    ("led_result", include_str!("scripts/led_result.txt")),
+    ("divider_part", include_str!("scripts/divider_part.txt")),
+];
+
+// Only used between frames, so it's never held while the game has the input
+fn input() -> &'static mut Input {
+    unsafe { game::input() }
+}
+
+// Everything runs in one test, as the game and the mock are both global
+#[test]
+fn replay() {
+    let mut failures = Vec::new();
+
+    for (name, script) in SCRIPTS {
+        host::reset();
+        host::set_eeprom(BANDS_ADDRESS, 4);
+        unsafe { game::setup() };
+
+        input().record();
+        if let Err(word) = input().play(script) {
+            panic!("{} isn't a button, in {}", word, name);
+        }
+        while input().replaying() {
+            unsafe { game::loop_() };
+        }
+        check(&format!("replay_{}.txt", name), &capture(), &mut failures);
+
+        // Recording the replay should give back the same script
+        let mut recorded = String::new();
+        input().write_recording(&mut recorded).unwrap();
+        if recorded != script {
+            failures.push(format!("{} was recorded as\n{}", name, recorded));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Replays don't match, run with UPDATE_SNAPSHOTS=1 and check the diff if the change is intended\n{}",
+        failures.join("\n")
+    );
+}
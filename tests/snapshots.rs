@@ -0,0 +1,153 @@
+// Renders screens of the game with the mock Arduboy and compares them with the goldens in
+// tests/snapshots, to catch layouts that overlap or move
+// Run with UPDATE_SNAPSHOTS=1 to write the goldens again after a deliberate change
+
+mod common;
+
+use arduboy_mock::host;
+use arduboy_mock::prelude::*;
+use common::*;
+use resistor_core::band::*;
+use std::fmt::Write;
+
+// Mode menu positions of the other banded parts
+const CAPACITOR_MODE: u8 = 10;
+const INDUCTOR_MODE: u8 = 11;
+
+// Runs one frame with the buttons held down
+fn frame(buttons: &[ButtonSet]) {
+    host::hold(buttons.iter().fold(0, |held, button| held | button.flag_set));
+    unsafe { game::loop_() };
+}
+
+// Holds the buttons for a frame then lets go, so the next press is new
+fn press(buttons: &[ButtonSet]) {
+    frame(buttons);
+    frame(&[]);
+}
+
+fn press_times(button: ButtonSet, times: u8) {
+    for _ in 0..times {
+        press(&[button]);
+    }
+}
+
+// Turns the game on with a number of bands saved
+fn start(bands: u8) {
+    host::reset();
+    host::set_eeprom(BANDS_ADDRESS, bands);
+    unsafe { game::setup() };
+    frame(&[]);
+}
+
+// Picks a mode from the menu opened by holding up and down then pressing B, from the colour mode
+fn open_mode(mode: u8) {
+    frame(&[UP, DOWN]);
+    frame(&[UP, DOWN, B]);
+    frame(&[]);
+    press_times(DOWN, mode);
+    press(&[A]);
+}
+
+// Shows the colour menu of the selected band with the arrow on every colour in turn
+fn capture_menu(vtype: ValType) -> String {
+    let colours = Band::colours_from_valtype(&vtype).len() as u8;
+    let mut frames = String::new();
+    press(&[A]);
+    for colour in 0..colours {
+        // Back to the top left, then down the rows and along to the colour
+        press_times(UP, colours / 3 + 1);
+        press_times(LEFT, 2);
+        press_times(DOWN, colour / 3);
+        press_times(RIGHT, colour % 3);
+        writeln!(frames, "Colour {}", colour).unwrap();
+        frames.push_str(&capture());
+    }
+    press(&[B]);
+    frames
+}
+
+// Everything runs in one test, as the game and the mock are both global
+#[test]
+fn snapshots() {
+    let mut failures = Vec::new();
+
+    // Every layout of the bands, with the text above each band
+    for bands in MIN_BANDS..=MAX_BANDS {
+        start(bands);
+        check(&format!("bands_{}.txt", bands), &capture(), &mut failures);
+    }
+    start(MIL_BANDS);
+    press(&[B]);
+    check("bands_mil.txt", &capture(), &mut failures);
+    start(JUMPER_BANDS);
+    check("bands_jumper.txt", &capture(), &mut failures);
+
+    // Every colour menu, selecting the band that uses it first
+    let resistor_menus = [
+        (0, ValType::Digit, "menu_digit.txt"),
+        (3, ValType::Multiplier, "menu_multiplier.txt"),
+        (4, ValType::Tolerance, "menu_tolerance.txt"),
+        (5, ValType::TCR, "menu_tcr.txt"),
+    ];
+    for (band, vtype, name) in resistor_menus {
+        start(MAX_BANDS);
+        press_times(RIGHT, band);
+        check(name, &capture_menu(vtype), &mut failures);
+    }
+
+    start(MIL_BANDS);
+    press(&[B]);
+    press_times(RIGHT, 4);
+    check(
+        "menu_failure_rate.txt",
+        &capture_menu(ValType::FailureRate),
+        &mut failures,
+    );
+
+    start(JUMPER_BANDS);
+    check("menu_jumper.txt", &capture_menu(ValType::Jumper), &mut failures);
+
+    // Saving the number of bands flashes the LED green, then it goes back to the band's colour
+    start(MIN_BANDS);
+    frame(&[LEFT, RIGHT]);
+    frame(&[LEFT, RIGHT, B]);
+    let mut saved = capture();
+    writeln!(saved, "EEPROM {}", host::eeprom()[BANDS_ADDRESS]).unwrap();
+    check("eeprom_confirm.txt", &saved, &mut failures);
+    for _ in 0..30 {
+        frame(&[]);
+    }
+    check("eeprom_confirm_after.txt", &capture(), &mut failures);
+
+    let capacitor_menus = [
+        (2, ValType::CapMultiplier, "menu_capacitor_multiplier.txt"),
+        (3, ValType::CapTolerance, "menu_capacitor_tolerance.txt"),
+        (4, ValType::Voltage, "menu_voltage.txt"),
+    ];
+    for (band, vtype, name) in capacitor_menus {
+        start(MAX_BANDS);
+        open_mode(CAPACITOR_MODE);
+        // Up from 4 to 5 bands, to show the voltage band
+        press(&[B]);
+        press_times(RIGHT, band);
+        check(name, &capture_menu(vtype), &mut failures);
+    }
+
+    let inductor_menus = [
+        (2, ValType::InductorMultiplier, "menu_inductor_multiplier.txt"),
+        (3, ValType::InductorTolerance, "menu_inductor_tolerance.txt"),
+    ];
+    for (band, vtype, name) in inductor_menus {
+        start(MAX_BANDS);
+        open_mode(INDUCTOR_MODE);
+        press_times(RIGHT, band);
+        check(name, &capture_menu(vtype), &mut failures);
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Snapshots don't match, run with UPDATE_SNAPSHOTS=1 and check the diff if the change is intended\n{}",
+        failures.join("\n")
+    );
+}